@@ -0,0 +1,20 @@
+use std::time::{Duration, Instant};
+
+/// Prints the time of a single run of `f`, the best of several batches of `runs` runs,
+/// which is less affected by whatever else runs on the machine than the mean.
+pub fn time(name: &str, runs: u32, mut f: impl FnMut()) {
+    f();
+
+    let best = (0..10)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..runs {
+                f();
+            }
+            start.elapsed() / runs
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    println!("{:<32} {:>12?}", name, best);
+}
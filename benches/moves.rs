@@ -0,0 +1,26 @@
+// Times the move functions on their own and within a full BFS.
+// Run with `cargo bench --bench moves`.
+
+mod common;
+
+use std::hint::black_box;
+
+use common::time;
+use superzub::{bfs, down, left, pack, right, scramble, up};
+
+fn main() {
+    let fields = (0..1000).map(|seed| scramble(40, seed)).collect::<Vec<_>>();
+    time("1000 fields x 4 moves", 1000, || {
+        for &field in &fields {
+            for f in &[up, down, left, right] {
+                black_box(f(black_box(field)));
+            }
+        }
+    });
+
+    let hardest = pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1]);
+    let goal = pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+    time("bfs, 31 moves", 3, || {
+        black_box(bfs(hardest, goal).unwrap());
+    });
+}
@@ -0,0 +1,48 @@
+// Times every search on the same puzzles, from a few moves to the 31 move worst case,
+// grouped by algorithm so the numbers can be compared side by side.
+// Run with `cargo bench --bench solve`.
+
+mod common;
+
+use std::hint::black_box;
+
+use common::time;
+use superzub::{astar, bfs, bidirectional_bfs, idastar, pack, scramble, ManhattanDistance};
+
+fn main() {
+    let goal = pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+    let puzzles = [
+        ("scrambled by 10", scramble(10, 7)),
+        ("scrambled by 20", scramble(20, 7)),
+        ("scrambled by 60", scramble(60, 7)),
+        ("31 moves", pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1])),
+    ];
+
+    println!("bfs");
+    for &(name, input) in &puzzles {
+        time(name, 3, || {
+            black_box(bfs(input, goal).unwrap());
+        });
+    }
+
+    println!("\nbidirectional bfs");
+    for &(name, input) in &puzzles {
+        time(name, 10, || {
+            black_box(bidirectional_bfs(input, goal).unwrap());
+        });
+    }
+
+    println!("\nastar, manhattan distance");
+    for &(name, input) in &puzzles {
+        time(name, 10, || {
+            black_box(astar(input, goal, ManhattanDistance).unwrap());
+        });
+    }
+
+    println!("\nidastar, manhattan distance");
+    for &(name, input) in &puzzles {
+        time(name, 3, || {
+            black_box(idastar(input, goal, ManhattanDistance));
+        });
+    }
+}
@@ -0,0 +1,22 @@
+// Times visiting the whole solvable half of the state space from the goal, which is dominated
+// by hashing the visited states. Compare the hashers with
+// `cargo bench --bench traversal` and `cargo bench --bench traversal --features fxhash`.
+
+mod common;
+
+use std::hint::black_box;
+
+use common::time;
+use superzub::{bfs_levels, bidirectional_bfs, pack, GOAL};
+
+fn main() {
+    let hardest = pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1]);
+
+    time("bfs levels", 1, || {
+        black_box(bfs_levels(GOAL));
+    });
+
+    time("bidirectional bfs, 31 moves", 10, || {
+        black_box(bidirectional_bfs(hardest, GOAL).unwrap());
+    });
+}
@@ -0,0 +1,85 @@
+"""Python bindings for the solver, calling the C interface from src/ffi.rs through ctypes.
+
+Build the library with
+
+    cargo rustc --release --lib --features ffi --crate-type cdylib
+
+and either keep it in target/release or point SUPERZUB_LIB at it. test_superzub.py next to this file
+runs a few smoke tests against it with `python3 -m unittest discover python`.
+
+    >>> import superzub
+    >>> superzub.solve([1, 2, 3, 4, 0, 5, 7, 8, 6])
+    ['R', 'D']
+    >>> len(superzub.scramble(20, 7))
+    9
+"""
+
+import ctypes
+import os
+import sys
+
+OK = 0
+NULL_POINTER = -1
+INVALID_INPUT = -2
+UNSOLVABLE = -3
+
+MAX_MOVES = 31
+
+DIRECTIONS = ["U", "D", "L", "R"]
+
+
+class SolveError(Exception):
+    """The puzzle has invalid tiles or can't reach the goal."""
+
+
+def _library_path():
+    if "SUPERZUB_LIB" in os.environ:
+        return os.environ["SUPERZUB_LIB"]
+
+    name = {"win32": "superzub.dll", "darwin": "libsuperzub.dylib"}.get(sys.platform, "libsuperzub.so")
+    root = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+    return os.path.join(root, "target", "release", name)
+
+
+_lib = ctypes.CDLL(_library_path())
+
+_lib.superzub_solve.argtypes = [
+    ctypes.POINTER(ctypes.c_uint32),
+    ctypes.POINTER(ctypes.c_uint8),
+    ctypes.POINTER(ctypes.c_size_t),
+]
+_lib.superzub_solve.restype = ctypes.c_int32
+
+_lib.superzub_scramble.argtypes = [ctypes.c_size_t, ctypes.c_uint64, ctypes.POINTER(ctypes.c_uint32)]
+_lib.superzub_scramble.restype = ctypes.c_int32
+
+
+def solve(tiles):
+    """Moves of the blank tile, each one of "U", "D", "L" and "R", solving the 9 tiles given in reading order,
+    0 being the blank tile."""
+    tiles = list(tiles)
+    if len(tiles) != 9:
+        raise SolveError("expected 9 tiles, found {}".format(len(tiles)))
+
+    moves = (ctypes.c_uint8 * MAX_MOVES)()
+    length = ctypes.c_size_t()
+    code = _lib.superzub_solve((ctypes.c_uint32 * 9)(*tiles), moves, ctypes.byref(length))
+
+    if code == INVALID_INPUT:
+        raise SolveError("tiles aren't a permutation of 0 to 8: {}".format(tiles))
+    if code == UNSOLVABLE:
+        raise SolveError("puzzle is unsolvable: {}".format(tiles))
+    if code != OK:
+        raise SolveError("unexpected error code {}".format(code))
+
+    return [DIRECTIONS[move] for move in moves[: length.value]]
+
+
+def scramble(moves, seed):
+    """Tiles of the goal after `moves` random moves, the same for the same seed."""
+    tiles = (ctypes.c_uint32 * 9)()
+    code = _lib.superzub_scramble(moves, seed, tiles)
+    if code != OK:
+        raise SolveError("unexpected error code {}".format(code))
+
+    return list(tiles)
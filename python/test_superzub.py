@@ -0,0 +1,48 @@
+"""Smoke tests for the bindings, run against a built library with
+
+    cargo rustc --release --lib --features ffi --crate-type cdylib
+    python3 -m unittest discover python
+"""
+
+import unittest
+
+import superzub
+
+
+def replay(tiles, moves):
+    tiles = list(tiles)
+    steps = {"U": -3, "D": 3, "L": -1, "R": 1}
+    for move in moves:
+        blank = tiles.index(0)
+        target = blank + steps[move]
+        tiles[blank], tiles[target] = tiles[target], tiles[blank]
+    return tiles
+
+
+class SolveTest(unittest.TestCase):
+    def test_solves_puzzles(self):
+        self.assertEqual(superzub.solve([1, 2, 3, 4, 0, 5, 7, 8, 6]), ["R", "D"])
+        self.assertEqual(superzub.solve([1, 2, 3, 4, 5, 6, 7, 8, 0]), [])
+
+        tiles = [8, 6, 7, 2, 5, 4, 3, 0, 1]
+        moves = superzub.solve(tiles)
+        self.assertEqual(len(moves), 31)
+        self.assertEqual(replay(tiles, moves), [1, 2, 3, 4, 5, 6, 7, 8, 0])
+
+    def test_raises_solve_errors(self):
+        with self.assertRaisesRegex(superzub.SolveError, "unsolvable"):
+            superzub.solve([1, 2, 3, 4, 5, 6, 8, 7, 0])
+        with self.assertRaisesRegex(superzub.SolveError, "permutation"):
+            superzub.solve([1, 1, 3, 4, 5, 6, 7, 8, 0])
+        with self.assertRaisesRegex(superzub.SolveError, "expected 9 tiles"):
+            superzub.solve([1, 2, 3])
+
+    def test_scrambles_reproducibly(self):
+        tiles = superzub.scramble(20, 7)
+        self.assertEqual(sorted(tiles), list(range(9)))
+        self.assertEqual(tiles, superzub.scramble(20, 7))
+        self.assertLessEqual(len(superzub.solve(tiles)), 20)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,76 @@
+use std::io::{BufRead, IsTerminal, Write};
+use std::time::Duration;
+
+use crate::{solve, Direction, SolveError, Trace};
+
+/// Plays the trace back on the terminal, redrawing each board in place after `delay_ms` milliseconds.
+///
+/// When stdout isn't a terminal the escape codes would only garble the output,
+/// so the boards are printed one after another without waiting instead.
+pub fn animate(trace: &Trace, delay_ms: u64) -> std::io::Result<()> {
+    let stdout = std::io::stdout();
+    let interactive = stdout.is_terminal();
+    let mut out = stdout.lock();
+
+    if !interactive {
+        return writeln!(out, "{}", trace);
+    }
+
+    // clear the screen once, then only move the cursor home so every board overwrites the last one
+    write!(out, "\x1b[2J")?;
+
+    let moves = trace.moves_count();
+    for (i, board) in trace.boards().enumerate() {
+        write!(out, "\x1b[H{}\nmove {}/{}\n", board, i, moves)?;
+        out.flush()?;
+
+        if i < moves {
+            std::thread::sleep(Duration::from_millis(delay_ms));
+        }
+    }
+
+    Ok(())
+}
+
+/// Solves the puzzle and walks through the solution a move at a time, printing the board
+/// and waiting for Enter before making the next move.
+///
+/// Stops early once stdin is closed or the terminal can't be written to.
+pub fn solve_interactive(input: &[u32; 9]) -> Result<(), SolveError> {
+    let trace = solve(input)?;
+
+    // the walk-through is best effort, there's nothing left to report if the terminal goes away
+    let _ = step_through(&trace);
+
+    Ok(())
+}
+
+fn step_through(trace: &Trace) -> std::io::Result<()> {
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut out = std::io::stdout();
+
+    let directions = trace.directions();
+    let mut boards = trace.boards();
+
+    writeln!(out, "{}", boards.next().unwrap())?;
+
+    for (i, (direction, board)) in directions.iter().zip(boards).enumerate() {
+        write!(out, "press Enter for move {} of {}", i + 1, directions.len())?;
+        out.flush()?;
+
+        if lines.next().transpose()?.is_none() {
+            return Ok(());
+        }
+
+        let name = match direction {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+        writeln!(out, "the blank moves {}\n{}", name, board)?;
+    }
+
+    writeln!(out, "solved")
+}
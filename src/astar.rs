@@ -0,0 +1,139 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::hash::HashMap;
+use crate::{
+    count_inversions, neighbors, unpack, Heuristic, SearchProgress, SearchStats, SolveError, Trace,
+    PROGRESS_INTERVAL,
+};
+
+/// Optimal trace from `input` to `output` for an admissible heuristic.
+///
+/// Among states of equal f-cost the one with the lower estimate h goes first, as it is likely closer
+/// to the goal, and after that the one with the lower packed value. The order of expansion is thereby fixed,
+/// so the same puzzle always gets the same trace, even where several optimal ones exist.
+///
+/// Returns `SolveError::Unsolvable` if the search runs out of states without reaching the output.
+pub fn astar(input: u32, output: u32, heuristic: impl Heuristic) -> Result<Trace, SolveError> {
+    astar_stats(input, output, heuristic).map(|(trace, _)| trace)
+}
+
+/// Same as `astar`, also reporting how much work the search took.
+pub fn astar_stats(
+    input: u32,
+    output: u32,
+    heuristic: impl Heuristic,
+) -> Result<(Trace, SearchStats), SolveError> {
+    astar_search(input, output, heuristic, 1.0, usize::MAX, None)
+}
+
+/// Weighted A*, ordering the states by `g + weight * h` instead of `g + h`, where g is the number of moves
+/// from the input and h is the heuristic's estimate of the moves left.
+///
+/// A weight of 1 is plain A*. Greater weights trust the heuristic more and head for the goal greedily,
+/// expanding fewer states, but then the solution can be longer than the optimal one, by a factor of up to `weight`
+/// for an admissible heuristic.
+pub fn astar_weighted(
+    input: u32,
+    output: u32,
+    heuristic: impl Heuristic,
+    weight: f64,
+) -> Result<Trace, SolveError> {
+    astar_search(input, output, heuristic, weight, usize::MAX, None).map(|(trace, _)| trace)
+}
+
+// f-cost that can be ordered in the queue, as weighted costs aren't integers anymore
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Priority(f64);
+
+impl Eq for Priority {}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+pub(crate) fn astar_search(
+    input: u32,
+    output: u32,
+    heuristic: impl Heuristic,
+    weight: f64,
+    max_nodes: usize,
+    mut progress: Option<&mut dyn FnMut(SearchProgress)>,
+) -> Result<(Trace, SearchStats), SolveError> {
+    let mut stats = SearchStats::default();
+
+    // state -> (parent, number of moves from input)
+    let mut tree = HashMap::default();
+    let mut queue = BinaryHeap::new();
+
+    tree.insert(input, (input, 0));
+    // ordered by f-cost, then by h and the packed field to break ties the same way every run
+    let key = |cost: u32, field| {
+        let h = heuristic.estimate(field, output);
+        (Priority(cost as f64 + weight * h as f64), h, field, cost)
+    };
+
+    queue.push(Reverse(key(0, input)));
+    stats.peak_frontier = queue.len();
+
+    while let Some(Reverse((_, _, current, cost))) = queue.pop() {
+        if current == output {
+            break;
+        }
+
+        // a shorter path to this state has been found after it was queued
+        if cost > tree[&current].1 {
+            continue;
+        }
+
+        if stats.expanded == max_nodes {
+            return Err(SolveError::LimitExceeded);
+        }
+
+        stats.expanded += 1;
+
+        for (_, value) in neighbors(current) {
+            let cost = cost + 1;
+
+            if tree.get(&value).is_none_or(|&(_, known)| cost < known) {
+                tree.insert(value, (current, cost));
+                queue.push(Reverse(key(cost, value)));
+                stats.generated += 1;
+            }
+        }
+
+        stats.peak_frontier = stats.peak_frontier.max(queue.len());
+
+        if stats.expanded.is_multiple_of(PROGRESS_INTERVAL) {
+            if let Some(progress) = progress.as_mut() {
+                let depth = cost as usize;
+                progress(SearchProgress { depth, expanded: stats.expanded, frontier: queue.len() });
+            }
+        }
+    }
+
+    // the queue ran dry, having gone through every state reachable from the input
+    if !tree.contains_key(&output) {
+        return Err(SolveError::Unsolvable { inversions: count_inversions(&unpack(input)) });
+    }
+
+    let mut current = output;
+    let mut trace = vec![current];
+
+    while current != tree[&current].0 {
+        current = tree[&current].0;
+        trace.push(current);
+    }
+
+    trace.reverse();
+
+    Ok((Trace::new(trace), stats))
+}
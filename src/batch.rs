@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use crate::{
+    check_solvability, distance_table, goal_array, neighbors, pack, validate_input, SolveError,
+    Trace, GOAL,
+};
+
+/// Summary of the outcomes of `solve_batch`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BatchStats {
+    pub solved: usize,
+    /// Puzzles rejected by the parity check.
+    pub unsolvable: usize,
+    /// Puzzles rejected for any other reason, such as invalid tiles.
+    pub invalid: usize,
+    /// Mean number of moves over the solved puzzles, 0 if there are none.
+    pub average_length: f64,
+    pub max_length: usize,
+}
+
+/// Solves every puzzle optimally, like `solve` does for a single one.
+///
+/// Computes the distance table once up front, after which each puzzle is solved by stepping
+/// to any neighbor a move closer to the goal, so it pays off for more than a handful of puzzles.
+pub fn solve_batch(inputs: &[[u32; 9]]) -> Vec<Result<Trace, SolveError>> {
+    let table = distance_table();
+    inputs.iter().map(|input| solve_with_table(input, &table)).collect()
+}
+
+/// Solves towards the standard goal using a table from `distance_table` or `load_table` as a perfect heuristic:
+/// every step goes to a neighbor one move closer, so no search is needed and the solution is optimal.
+///
+/// Returns `SolveError::IncompleteTable` if the table misses any of the states on the way,
+/// or has none of them a move closer to the goal than the previous one.
+pub fn solve_with_table(input: &[u32; 9], table: &HashMap<u32, u8>) -> Result<Trace, SolveError> {
+    validate_input(input)?;
+    check_solvability(input, &goal_array())?;
+
+    let mut current = pack(input);
+    let mut trace = vec![current];
+
+    while current != GOAL {
+        let closer = table
+            .get(&current)
+            .and_then(|distance| distance.checked_sub(1))
+            .ok_or(SolveError::IncompleteTable)?;
+        current = neighbors(current)
+            .map(|(_, value)| value)
+            .find(|value| table.get(value) == Some(&closer))
+            .ok_or(SolveError::IncompleteTable)?;
+        trace.push(current);
+    }
+
+    Ok(Trace::new(trace))
+}
+
+pub fn batch_stats(results: &[Result<Trace, SolveError>]) -> BatchStats {
+    let mut stats = BatchStats::default();
+    let mut total_length = 0;
+
+    for result in results {
+        match result {
+            Ok(trace) => {
+                let length = trace.moves_count();
+                stats.solved += 1;
+                stats.max_length = stats.max_length.max(length);
+                total_length += length;
+            }
+            Err(SolveError::Unsolvable { .. }) => stats.unsolvable += 1,
+            Err(_) => stats.invalid += 1,
+        }
+    }
+
+    if stats.solved > 0 {
+        stats.average_length = total_length as f64 / stats.solved as f64;
+    }
+
+    stats
+}
@@ -0,0 +1,77 @@
+use crate::hash::HashMap;
+use crate::{count_inversions, neighbors, unpack, SolveError, Trace};
+
+/// Breadth-first search from both ends at once, meeting in the middle.
+/// Each side only has to reach about half the depth, so far fewer states are visited than by `bfs`.
+///
+/// Returns `SolveError::Unsolvable` if either search runs out of states before they meet.
+pub fn bidirectional_bfs(input: u32, output: u32) -> Result<Trace, SolveError> {
+    // state -> (parent, depth), for the searches from the input and from the output
+    let mut forward = HashMap::default();
+    let mut backward = HashMap::default();
+    forward.insert(input, (input, 0));
+    backward.insert(output, (output, 0));
+
+    let mut forward_frontier = vec![input];
+    let mut backward_frontier = vec![output];
+
+    let mut meeting = (input == output).then_some(input);
+
+    while meeting.is_none() {
+        // always grow the smaller frontier by a whole layer
+        let (frontier, tree, other) = if forward_frontier.len() <= backward_frontier.len() {
+            (&mut forward_frontier, &mut forward, &backward)
+        } else {
+            (&mut backward_frontier, &mut backward, &forward)
+        };
+
+        // a search that ran dry has gone through every state reachable from its end
+        if frontier.is_empty() {
+            return Err(SolveError::Unsolvable { inversions: count_inversions(&unpack(input)) });
+        }
+
+        // the first layer to touch the other search may do so more than once,
+        // the shortest path goes through the meeting state closest to both ends
+        let mut best = None;
+        let mut next = Vec::new();
+
+        for &current in frontier.iter() {
+            let depth = tree[&current].1 + 1;
+
+            for (_, value) in neighbors(current) {
+                if tree.contains_key(&value) {
+                    continue;
+                }
+
+                tree.insert(value, (current, depth));
+                next.push(value);
+
+                if let Some(&(_, rest)) = other.get(&value) {
+                    if best.is_none_or(|(length, _)| depth + rest < length) {
+                        best = Some((depth + rest, value));
+                    }
+                }
+            }
+        }
+
+        *frontier = next;
+        meeting = best.map(|(_, value)| value);
+    }
+
+    let meeting = meeting.unwrap();
+    let path = |tree: &HashMap<u32, (u32, usize)>| {
+        let mut current = meeting;
+        let mut path = vec![current];
+        while tree[&current].0 != current {
+            current = tree[&current].0;
+            path.push(current);
+        }
+        path
+    };
+
+    let mut trace = path(&forward);
+    trace.reverse();
+    trace.extend(path(&backward).into_iter().skip(1));
+
+    Ok(Trace::new(trace))
+}
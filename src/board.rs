@@ -0,0 +1,237 @@
+use alloc::vec::Vec;
+
+use itertools::Itertools;
+
+use crate::factoriadic::checked_fact;
+use crate::idastar::ida;
+use crate::{check_tiles, SolveError};
+
+// Generalization of the 3 by 3 packing from lib.rs to boards of R rows and C columns.
+//
+// Every cell takes just enough bits to hold the largest tile index (R * C - 2), so
+// a 2x3 or 3x3 board uses 3 bits per cell, 4x4 uses 4 bits and 5x5 uses 5 bits. All cells fit
+// into a u128 up to 5x5 (25 * 5 = 125 bits), which leaves no room for the blank position,
+// so unlike the u32 field it is stored separately. As before, bits owned by the blank tile are zeros.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Board<const R: usize, const C: usize> {
+    field: u128,
+    blank: u8,
+}
+
+impl<const R: usize, const C: usize> Board<R, C> {
+    pub const CELLS: usize = R * C;
+
+    const BITS: usize = (usize::BITS - (R * C - 2).leading_zeros()) as usize;
+
+    // a single row or column can't reorder its tiles, which the solvability check doesn't account for
+    const FITS: () = assert!(
+        R >= 2 && C >= 2 && R * C * Self::BITS <= 128,
+        "board must be at least 2x2 and fit into u128"
+    );
+
+    #[inline(always)]
+    const fn get_mask(i: usize) -> u128 {
+        ((1 << Self::BITS) - 1) << (i * Self::BITS)
+    }
+
+    #[inline(always)]
+    pub const fn get_blank_pos(self) -> usize {
+        self.blank as usize
+    }
+
+    #[inline(always)]
+    pub const fn get_tile(self, i: usize) -> u32 {
+        ((self.field & Self::get_mask(i)) >> (i * Self::BITS)) as u32
+    }
+
+    /// Row and column of the blank tile, counted from the top left corner.
+    pub const fn blank_coords(self) -> (usize, usize) {
+        let pos = self.get_blank_pos();
+        (pos / C, pos % C)
+    }
+
+    /// Row and column of the tile with the given value, 0 being the blank tile.
+    ///
+    /// Panics if the value isn't on the board.
+    pub fn tile_coords(self, tile: u32) -> (usize, usize) {
+        if tile == 0 {
+            return self.blank_coords();
+        }
+
+        let blank = self.get_blank_pos();
+        let pos = (0..Self::CELLS)
+            .find(|&i| i != blank && self.get_tile(i) == tile - 1)
+            .unwrap_or_else(|| panic!("tile {} isn't on the board", tile));
+        (pos / C, pos % C)
+    }
+
+    /// Packs the rows of the board, where 0 is the blank tile.
+    pub fn from_2d(grid: [[u32; C]; R]) -> Result<Self, SolveError> {
+        Self::pack(&grid.concat())
+    }
+
+    /// Packs a flat row-major slice, where 0 is the blank tile. The slice has to be a permutation of 0..R*C.
+    pub fn pack(input: &[u32]) -> Result<Self, SolveError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::FITS;
+
+        validate_input(input, Self::CELLS)?;
+
+        Ok(input.iter().enumerate().fold(
+            Board { field: 0, blank: 0 },
+            |mut board, (index, &tile)| {
+                if tile == 0 {
+                    board.blank = index as u8;
+                } else {
+                    board.field |= ((tile - 1) as u128) << (index * Self::BITS);
+                }
+                board
+            },
+        ))
+    }
+
+    /// Tiles 1..R*C-1 in reading order with the blank tile in the bottom right corner.
+    pub fn goal() -> Self {
+        let tiles = (1..Self::CELLS as u32).chain(core::iter::once(0)).collect::<Vec<_>>();
+        Self::pack(&tiles).unwrap()
+    }
+
+    fn make_move(mut self, in_bounds: fn(usize) -> bool, delta_pos: isize) -> Self {
+        let blank_pos = self.get_blank_pos();
+
+        if !in_bounds(blank_pos) {
+            return self;
+        }
+
+        let new_pos = (blank_pos as isize + delta_pos) as usize;
+
+        // move the tile into the old blank cell and clean up the new one
+        let tile = self.field & Self::get_mask(new_pos);
+        self.field &= !Self::get_mask(new_pos);
+        self.field |= if delta_pos > 0 {
+            tile >> (delta_pos as usize * Self::BITS)
+        } else {
+            tile << (-delta_pos as usize * Self::BITS)
+        };
+        self.blank = new_pos as u8;
+
+        self
+    }
+
+    pub fn up(self) -> Self {
+        self.make_move(|pos| pos >= C, -(C as isize))
+    }
+
+    pub fn down(self) -> Self {
+        self.make_move(|pos| pos < R * C - C, C as isize)
+    }
+
+    pub fn left(self) -> Self {
+        self.make_move(|pos| pos % C != 0, -1)
+    }
+
+    pub fn right(self) -> Self {
+        self.make_move(|pos| pos % C != C - 1, 1)
+    }
+
+    /// Sum of Manhattan distances of the tiles to their cells in `Self::goal()`, where tile index i belongs to cell i.
+    pub fn manhattan(self) -> u32 {
+        let blank = self.get_blank_pos();
+        (0..Self::CELLS)
+            .filter(|&i| i != blank)
+            .map(|i| {
+                let target = self.get_tile(i) as usize;
+                ((i / C).abs_diff(target / C) + (i % C).abs_diff(target % C)) as u32
+            })
+            .sum()
+    }
+
+    pub fn is_solvable(self) -> bool {
+        check_solvability(self).is_ok()
+    }
+
+    /// Solves the board with IDA*, returning every state from the input to the goal.
+    /// Unlike BFS this doesn't keep the visited states around, so it scales to 4x4 boards.
+    pub fn solve(input: &[u32]) -> Result<Vec<Self>, SolveError> {
+        Self::solve_with(input, Self::manhattan)
+    }
+
+    /// Same as `solve` with another estimate of the moves left, e.g. `PatternDatabase::estimate`.
+    /// The solution is only optimal if the estimate never exceeds the actual number of moves.
+    pub fn solve_with(
+        input: &[u32],
+        heuristic: impl Fn(Self) -> u32,
+    ) -> Result<Vec<Self>, SolveError> {
+        let input = Self::pack(input)?;
+        check_solvability(input)?;
+
+        let moves = [Self::up, Self::down, Self::left, Self::right];
+        ida(input, Self::goal(), &moves, heuristic)
+            .ok_or_else(|| SolveError::Unsolvable { inversions: inversions(input) })
+    }
+}
+
+impl<const R: usize, const C: usize> core::fmt::Display for Board<R, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for i in 0..Self::CELLS {
+            if i != self.get_blank_pos() {
+                write!(f, "{:2} ", self.get_tile(i))?;
+            } else {
+                write!(f, "   ")?;
+            }
+            if i % C == C - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Number of states reachable from the goal of an N by N board, (N * N)! / 2: every arrangement of the tiles
+/// has a twin with two tiles swapped, and exactly one of the two is solvable. Meant for N of at least 2.
+///
+/// Panics if the count overflows u128, which happens past N = 5.
+pub fn solvable_count(n: usize) -> u128 {
+    let arrangements = checked_fact(n * n).expect("number of arrangements overflows u128");
+    arrangements / 2
+}
+
+fn validate_input(input: &[u32], cells: usize) -> Result<(), SolveError> {
+    if input.len() != cells {
+        return Err(SolveError::WrongTileCount(input.len()));
+    }
+    check_tiles(input)
+}
+
+// pairs of tiles where the greater one comes first in reading order, ignoring the blank tile
+fn inversions<const R: usize, const C: usize>(input: Board<R, C>) -> usize {
+    let blank = input.get_blank_pos();
+    let tiles = (0..Board::<R, C>::CELLS)
+        .filter(|&i| i != blank)
+        .map(|i| input.get_tile(i))
+        .collect::<Vec<_>>();
+
+    tiles.iter().tuple_combinations().filter(|(a, b)| a > b).count()
+}
+
+/// Same parity argument as `check_solvability` for the 3 by 3 field, which only holds for an odd number
+/// of columns C, as a vertical move takes a tile past C - 1 others. The number of rows doesn't matter.
+///
+/// For even C a horizontal move still keeps the parity of inversions, but a vertical one moves
+/// a tile past an odd number of others, flipping it, while also changing the blank's row.
+/// So the parity of (inversions + row of the blank counted from the bottom, starting with 1) is invariant,
+/// and it is odd for the goal, where there are no inversions and the blank is on the bottom row.
+fn check_solvability<const R: usize, const C: usize>(input: Board<R, C>) -> Result<(), SolveError> {
+    let blank = input.get_blank_pos();
+    let inversions = inversions(input);
+
+    let solvable = if C % 2 == 1 {
+        inversions % 2 == 0
+    } else {
+        let row_from_bottom = R - blank / C;
+        (inversions + row_from_bottom) % 2 == 1
+    };
+
+    solvable.then_some(()).ok_or(SolveError::Unsolvable { inversions })
+}
@@ -0,0 +1,250 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::hash::HashSet;
+use crate::{
+    bfs, canonical, check_solvability, fact, is_packed, neighbors, random::Rng, unpack,
+    SearchProgress, GOAL, PROGRESS_INTERVAL,
+};
+
+/// Optimal number of moves between two arbitrary states, or `None` if the parity of their inversions differs
+/// and neither can be reached from the other.
+pub fn distance(a: u32, b: u32) -> Option<usize> {
+    check_solvability(&unpack(a), &unpack(b)).ok()?;
+    bfs(a, b).ok().map(|trace| trace.moves_count())
+}
+
+/// Optimal number of moves to the goal for every solvable state, found by a complete BFS from the goal.
+///
+/// Takes a while and holds all 9!/2 states, so it's meant to be computed once and kept around,
+/// after which looking up a state is a perfect heuristic.
+pub fn distance_table() -> HashMap<u32, u8> {
+    distance_table_with_progress(None)
+}
+
+/// Same as `distance_table`, calling `progress` periodically while the search runs.
+pub fn distance_table_with_progress(
+    mut progress: Option<&mut dyn FnMut(SearchProgress)>,
+) -> HashMap<u32, u8> {
+    let mut table = HashMap::with_capacity(fact(9) / 2);
+    let mut moves = VecDeque::with_capacity(fact(9) / 2);
+
+    table.insert(GOAL, 0);
+    moves.push_back(GOAL);
+
+    let mut expanded = 0usize;
+
+    while let Some(current) = moves.pop_front() {
+        let distance = table[&current] + 1;
+        expanded += 1;
+
+        for (_, value) in neighbors(current) {
+            table.entry(value).or_insert_with(|| {
+                moves.push_back(value);
+                distance
+            });
+        }
+
+        if expanded.is_multiple_of(PROGRESS_INTERVAL) {
+            if let Some(progress) = progress.as_mut() {
+                let depth = distance as usize - 1;
+                progress(SearchProgress { depth, expanded, frontier: moves.len() });
+            }
+        }
+    }
+
+    table
+}
+
+/// Same as `distance_table`, but only keeps canonical states, so a state's distance is found
+/// under `canonical(state)`. Storing one state for each pair the relabeled transposition swaps
+/// takes a bit more than half of the memory.
+pub fn distance_table_canonical() -> HashMap<u32, u8> {
+    let mut table = HashMap::with_capacity(fact(9) / 4);
+    let mut moves = VecDeque::with_capacity(fact(9) / 4);
+
+    table.insert(canonical(GOAL), 0);
+    moves.push_back(canonical(GOAL));
+
+    while let Some(current) = moves.pop_front() {
+        let distance = table[&current] + 1;
+
+        // the neighbors of the other state of the pair are the transposed neighbors of this one
+        for (_, value) in neighbors(current) {
+            let value = canonical(value);
+            table.entry(value).or_insert_with(|| {
+                moves.push_back(value);
+                distance
+            });
+        }
+    }
+
+    table
+}
+
+/// Writes the table as a sequence of 5-byte records, one per state in ascending order:
+/// the packed field as a little-endian u32 followed by its distance as a single byte.
+/// There is no header, so a table of all solvable states takes 181440 * 5 bytes.
+pub fn save_table(table: &HashMap<u32, u8>, path: &Path) -> io::Result<()> {
+    let mut entries = table.iter().map(|(&field, &distance)| (field, distance)).collect::<Vec<_>>();
+    entries.sort_unstable();
+
+    let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+    for (field, distance) in entries {
+        out.write_all(&field.to_le_bytes())?;
+        out.write_all(&[distance])?;
+    }
+    out.flush()
+}
+
+/// Reads a table written by `save_table`.
+///
+/// Fails with `InvalidData` unless the records are validly packed fields in ascending order, no more than
+/// there are solvable states, with distances of at most 31 moves, and the goal among them at distance 0.
+/// The distances aren't checked against each other, which `solve_with_table` reports instead.
+pub fn load_table(path: &Path) -> io::Result<HashMap<u32, u8>> {
+    let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)?.read_to_end(&mut bytes)?;
+
+    if !bytes.len().is_multiple_of(5) {
+        return Err(invalid("file isn't made of 5-byte records"));
+    }
+    if bytes.len() / 5 > fact(9) / 2 {
+        return Err(invalid("file has more records than there are solvable states"));
+    }
+
+    let records = bytes
+        .chunks(5)
+        .map(|record| (u32::from_le_bytes([record[0], record[1], record[2], record[3]]), record[4]))
+        .collect::<Vec<_>>();
+
+    for &(field, distance) in &records {
+        if !is_packed(field) {
+            return Err(invalid("record holds an invalid state"));
+        }
+        if distance > 31 {
+            return Err(invalid("record holds a distance over 31 moves"));
+        }
+    }
+    if records.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err(invalid("records aren't in ascending order"));
+    }
+
+    let table = records.into_iter().collect::<HashMap<_, _>>();
+    if table.get(&GOAL) != Some(&0) {
+        return Err(invalid("goal isn't at distance 0"));
+    }
+
+    Ok(table)
+}
+
+/// Same as `distance_table`, but every BFS level's neighbors are generated on all available threads.
+/// The threads only read the states found so far, and the new ones are deduplicated once they are joined.
+#[cfg(feature = "parallel")]
+pub fn distance_table_parallel() -> HashMap<u32, u8> {
+    let threads = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+
+    let mut table = HashMap::with_capacity(fact(9) / 2);
+    let mut frontier = vec![GOAL];
+    let mut distance = 0;
+
+    table.insert(GOAL, 0);
+
+    while !frontier.is_empty() {
+        distance += 1;
+
+        let chunk_size = frontier.len().div_ceil(threads);
+        let found = std::thread::scope(|scope| {
+            let table = &table;
+            let handles = frontier
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .flat_map(|&field| neighbors(field).map(|(_, value)| value))
+                            .filter(|value| !table.contains_key(value))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        frontier = found
+            .into_iter()
+            .flatten()
+            .filter(|&value| table.insert(value, distance).is_none())
+            .collect();
+    }
+
+    table
+}
+
+/// States grouped by their distance from `output`: `levels[d]` holds every state exactly d moves away,
+/// in the order BFS discovers them. Covers the whole reachable half of the state space, 32 levels from the goal.
+pub fn bfs_levels(output: u32) -> Vec<Vec<u32>> {
+    bfs_levels_limited(output, usize::MAX)
+}
+
+/// Same as `bfs_levels`, stopping after the level `max_depth` moves away to bound the memory taken.
+pub fn bfs_levels_limited(output: u32, max_depth: usize) -> Vec<Vec<u32>> {
+    let mut visited = HashSet::default();
+    visited.insert(output);
+
+    let mut levels = vec![vec![output]];
+
+    while levels.len() <= max_depth {
+        let next = levels[levels.len() - 1]
+            .iter()
+            .flat_map(|&field| neighbors(field).map(|(_, value)| value))
+            .filter(|&value| visited.insert(value))
+            .collect::<Vec<_>>();
+
+        if next.is_empty() {
+            break;
+        }
+        levels.push(next);
+    }
+
+    levels
+}
+
+/// Uniformly random state whose optimal solution is exactly `d` moves long, the same for the same seed.
+/// Unlike `scramble`, where random moves can cancel each other out, the distance is guaranteed.
+///
+/// Panics if `d` is greater than 31, as no state is that far from the goal.
+pub fn shuffle_to_distance(d: u8, seed: u64) -> u32 {
+    let levels = bfs_levels_limited(GOAL, d as usize);
+    let states = levels.get(d as usize).unwrap_or_else(|| panic!("no state is {} moves away", d));
+
+    states[Rng::new(seed).below(states.len() as u64) as usize]
+}
+
+/// Length of the longest optimal solution and every state that needs that many moves, in ascending order.
+pub fn hardest_states() -> (u8, Vec<u32>) {
+    let table = distance_table();
+    let max = table.values().copied().max().unwrap_or(0);
+
+    let mut states = table
+        .into_iter()
+        .filter(|&(_, distance)| distance == max)
+        .map(|(field, _)| field)
+        .collect::<Vec<_>>();
+    states.sort_unstable();
+
+    (max, states)
+}
+
+/// Number of solvable states for every optimal distance from the goal, which is at most 31.
+pub fn distance_histogram() -> [u64; 32] {
+    let mut histogram = [0; 32];
+    for distance in distance_table().into_values() {
+        histogram[distance as usize] += 1;
+    }
+    histogram
+}
@@ -0,0 +1,277 @@
+// Permutations numbered in lexicographic order through the factorial number system.
+//
+// A number below n! has a unique representation d[n-1]·(n-1)! + ... + d[1]·1! + d[0]·0!, where d[i] <= i.
+// Reading the digits from the most significant one, each of them picks which of the still unused
+// elements comes next, so the digits of i spell out the i-th permutation (its Lehmer code).
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// x!, or None if it overflows u128, which happens past 34!.
+pub fn checked_fact(x: usize) -> Option<u128> {
+    (1..=x as u128).try_fold(1u128, |product, i| product.checked_mul(i))
+}
+
+// ranks of permutations are counted in u128, which fits every rank of up to 34 elements, e.g. of a 5x5 board
+fn weight(len: usize) -> u128 {
+    checked_fact(len).expect("permutation is too long to rank in u128")
+}
+
+/// The i-th permutation of `from` in lexicographic order of positions in `from`.
+pub fn get_ith<T: Clone>(from: &[T], mut i: u128) -> Vec<T> {
+    let mut from = from.to_vec();
+    let mut perm = Vec::with_capacity(from.len());
+
+    while !from.is_empty() {
+        let weight = weight(from.len() - 1);
+        perm.push(from.remove((i / weight) as usize));
+        i %= weight;
+    }
+
+    perm
+}
+
+/// Index of `perm` among the permutations of `alphabet`, the inverse of `get_ith`.
+///
+/// Panics if `perm` isn't a permutation of `alphabet`.
+pub fn rank_of<T: PartialEq>(perm: &[T], alphabet: &[T]) -> u128 {
+    assert_eq!(perm.len(), alphabet.len(), "permutation and alphabet differ in length");
+
+    let mut left = alphabet.iter().collect::<Vec<_>>();
+
+    perm.iter().fold(0, |rank, x| {
+        let digit =
+            left.iter().position(|&y| y == x).expect("element is missing from the alphabet");
+        left.remove(digit);
+        rank + digit as u128 * weight(left.len())
+    })
+}
+
+/// Lexicographic index of a permutation of 0..n.
+pub fn rank(perm: &[u32]) -> u128 {
+    lehmer(perm).enumerate().map(|(i, digit)| digit as u128 * weight(perm.len() - 1 - i)).sum()
+}
+
+/// Whether `perm` is even, i.e. has an even number of inversions.
+/// Only the order of the values matters, so they don't have to be exactly 0..n.
+pub fn parity(perm: &[u32]) -> bool {
+    // every digit of the Lehmer code counts the inversions the element takes part in as the greater one
+    lehmer(perm).sum::<u64>() % 2 == 0
+}
+
+// digits of the factoriadic rank, most significant first: how many of the later elements are smaller
+fn lehmer(perm: &[u32]) -> impl Iterator<Item = u64> + '_ {
+    perm.iter()
+        .enumerate()
+        .map(move |(i, &x)| perm[i + 1..].iter().filter(|&&y| y < x).count() as u64)
+}
+
+/// The permutation of 0..n with the given lexicographic index, the inverse of `rank`.
+pub fn unrank(index: u128, n: usize) -> Vec<u32> {
+    let identity = (0..n as u32).collect::<Vec<_>>();
+    get_ith(&identity, index)
+}
+
+/// Permutation applying `b` first and `a` second: `compose(a, b)[i] == a[b[i]]`.
+///
+/// Panics if the permutations differ in length.
+pub fn compose(a: &[u32], b: &[u32]) -> Vec<u32> {
+    assert_eq!(a.len(), b.len(), "permutations differ in length");
+    b.iter().map(|&i| a[i as usize]).collect()
+}
+
+/// Permutation undoing `p`: `inverse(p)[p[i]] == i`.
+pub fn inverse(p: &[u32]) -> Vec<u32> {
+    let mut inverse = vec![0; p.len()];
+    for (i, &x) in p.iter().enumerate() {
+        inverse[x as usize] = i as u32;
+    }
+    inverse
+}
+
+/// Rearranges `perm` into the permutation following it in lexicographic order.
+///
+/// Returns false, leaving `perm` sorted ascending, if it was the last one.
+pub fn next_permutation(perm: &mut [u32]) -> bool {
+    step(perm, |a, b| a < b)
+}
+
+/// Rearranges `perm` into the permutation preceding it in lexicographic order.
+///
+/// Returns false, leaving `perm` sorted descending, if it was the first one.
+pub fn prev_permutation(perm: &mut [u32]) -> bool {
+    step(perm, |a, b| a > b)
+}
+
+// the longest suffix out of order is the last to be permuted: the element right before it
+// swaps with the closest one in order from the suffix, which is then reversed to its first arrangement
+fn step(perm: &mut [u32], in_order: impl Fn(u32, u32) -> bool) -> bool {
+    let pivot = match perm.windows(2).rposition(|pair| in_order(pair[0], pair[1])) {
+        Some(pivot) => pivot,
+        None => {
+            perm.reverse();
+            return false;
+        }
+    };
+
+    let successor = perm.iter().rposition(|&x| in_order(perm[pivot], x)).unwrap();
+    perm.swap(pivot, successor);
+    perm[pivot + 1..].reverse();
+
+    true
+}
+
+/// Digits of `n` in the factorial number system, most significant first and separated by colons,
+/// e.g. `"3:4:1:0:1:0!"` for 463 = 3·5! + 4·4! + 1·3! + 0·2! + 1·1! + 0·0!.
+/// The last digit weighs 0! and is always 0.
+pub fn to_radix_string(n: u64) -> String {
+    let digits = Factoriadic::new(n).digits.iter().rev().map(u64::to_string).collect::<Vec<_>>();
+    format!("{}!", digits.join(":"))
+}
+
+/// Number in the factorial number system, least significant digit first, so `digits[i]` weighs i!.
+#[derive(Debug, Clone)]
+pub struct Factoriadic {
+    digits: Vec<u64>,
+}
+
+impl Factoriadic {
+    pub fn new(mut n: u64) -> Self {
+        let mut digits = Vec::new();
+        let mut radix = 1;
+
+        loop {
+            digits.push(n % radix);
+            n /= radix;
+            radix += 1;
+
+            if n == 0 {
+                break Factoriadic { digits };
+            }
+        }
+    }
+
+    /// Number from its digits, least significant first, rejecting any digit i greater than i.
+    pub fn from_digits(mut digits: Vec<u64>) -> Result<Self, String> {
+        if let Some((i, digit)) = digits.iter().enumerate().find(|&(i, &digit)| digit > i as u64) {
+            return Err(format!("digit {} in place {} exceeds the radix {}", digit, i, i + 1));
+        }
+
+        if digits.is_empty() {
+            digits.push(0);
+        }
+
+        Ok(Factoriadic { digits })
+    }
+
+    /// Value of the number, the inverse of `new`.
+    ///
+    /// # Panics
+    ///
+    /// If the value doesn't fit in u64. Leading zeros don't count, however many there are.
+    pub fn to_u64(&self) -> u64 {
+        let value =
+            self.digits.iter().enumerate().filter(|&(_, &digit)| digit != 0).try_fold(
+                0u128,
+                |value, (i, &digit)| {
+                    checked_fact(i)?.checked_mul(digit as u128)?.checked_add(value)
+                },
+            );
+
+        value.and_then(|value| u64::try_from(value).ok()).expect("factoriadic overflows u64")
+    }
+
+    pub fn digits(&self) -> &[u64] {
+        &self.digits
+    }
+
+    // digits without the leading zeros, so equal values compare equal whatever their length
+    fn significant(&self) -> &[u64] {
+        let len = self.digits.iter().rposition(|&digit| digit != 0).map_or(0, |i| i + 1);
+        &self.digits[..len]
+    }
+}
+
+impl PartialEq for Factoriadic {
+    fn eq(&self, other: &Self) -> bool {
+        self.significant() == other.significant()
+    }
+}
+
+impl Eq for Factoriadic {}
+
+impl PartialOrd for Factoriadic {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Factoriadic {
+    /// A longer number is greater, as its top digit outweighs all the lower ones together:
+    /// the sum of i·i! below n is n! - 1. Numbers of equal length compare from the top digit down.
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let (a, b) = (self.significant(), other.significant());
+        a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+}
+
+impl core::ops::Add for Factoriadic {
+    type Output = Factoriadic;
+
+    /// Digit-wise addition, where a digit in place i carries over once it reaches its radix i + 1.
+    /// The shorter operand is treated as padded with zeros.
+    fn add(self, other: Self) -> Self {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |f: &Self, i: usize| f.digits.get(i).copied().unwrap_or(0);
+
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+
+        for i in 0.. {
+            if i >= len && carry == 0 {
+                break;
+            }
+
+            let radix = i as u64 + 1;
+            let sum = digit(&self, i) + digit(&other, i) + carry;
+            digits.push(sum % radix);
+            carry = sum / radix;
+        }
+
+        Factoriadic { digits }
+    }
+}
+
+impl core::ops::Sub for Factoriadic {
+    type Output = Factoriadic;
+
+    /// Digit-wise subtraction, borrowing the radix i + 1 from the next digit when digit i goes negative.
+    ///
+    /// Panics if `other` is greater than `self`, like unsigned integer subtraction.
+    fn sub(self, other: Self) -> Self {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |f: &Self, i: usize| f.digits.get(i).copied().unwrap_or(0);
+
+        let mut digits = Vec::with_capacity(len);
+        let mut borrow = 0;
+
+        for i in 0..len {
+            let radix = i as u64 + 1;
+            let (a, b) = (digit(&self, i), digit(&other, i) + borrow);
+
+            borrow = (a < b) as u64;
+            digits.push(a + borrow * radix - b);
+        }
+
+        assert!(borrow == 0, "attempt to subtract a greater factoriadic");
+
+        while digits.len() > 1 && digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Factoriadic { digits }
+    }
+}
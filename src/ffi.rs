@@ -0,0 +1,79 @@
+// C interface to the solver, laid out so that cbindgen can generate a header for it.
+//
+// The crate is built as an rlib, so a library for C has to be asked for explicitly, e.g.
+// `cargo rustc --release --lib --features ffi --crate-type cdylib` (or `staticlib`).
+// Errors are reported as negative return codes, as nothing can unwind across the boundary.
+
+use core::convert::TryInto;
+
+use crate::{scramble, solve, unpack, Direction, SolveError};
+
+pub const SUPERZUB_OK: i32 = 0;
+/// One of the pointers is null.
+pub const SUPERZUB_NULL_POINTER: i32 = -1;
+/// The tiles aren't a permutation of 0..=8.
+pub const SUPERZUB_INVALID_INPUT: i32 = -2;
+/// The parity of inversions differs from the goal's.
+pub const SUPERZUB_UNSOLVABLE: i32 = -3;
+
+/// Longest optimal solution of the 8-puzzle, which is enough room for the moves of any of them.
+pub const SUPERZUB_MAX_MOVES: usize = 31;
+
+/// Solves the puzzle given as 9 tiles in reading order, 0 being the blank tile, and writes the moves
+/// of the blank tile to `out_moves` as 0 for up, 1 for down, 2 for left and 3 for right.
+/// The number of moves goes to `out_len`.
+///
+/// Returns `SUPERZUB_OK` or one of the negative error codes, in which case the outputs are left untouched.
+///
+/// # Safety
+///
+/// `input` has to point to 9 readable values, `out_moves` to `SUPERZUB_MAX_MOVES` writable bytes
+/// and `out_len` to a writable value, unless they are null.
+#[no_mangle]
+pub unsafe extern "C" fn superzub_solve(
+    input: *const u32,
+    out_moves: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if input.is_null() || out_moves.is_null() || out_len.is_null() {
+        return SUPERZUB_NULL_POINTER;
+    }
+
+    let input: &[u32; 9] = core::slice::from_raw_parts(input, 9).try_into().unwrap();
+    let trace = match solve(input) {
+        Ok(trace) => trace,
+        Err(SolveError::Unsolvable { .. }) => return SUPERZUB_UNSOLVABLE,
+        Err(_) => return SUPERZUB_INVALID_INPUT,
+    };
+
+    let directions = trace.directions();
+    let moves = core::slice::from_raw_parts_mut(out_moves, directions.len());
+    for (byte, direction) in moves.iter_mut().zip(&directions) {
+        *byte = match direction {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        };
+    }
+    *out_len = directions.len();
+
+    SUPERZUB_OK
+}
+
+/// Writes the 9 tiles of the puzzle `scramble` makes with the same arguments to `out_tiles`, in reading order.
+///
+/// # Safety
+///
+/// `out_tiles` has to point to 9 writable values, unless it is null.
+#[no_mangle]
+pub unsafe extern "C" fn superzub_scramble(moves: usize, seed: u64, out_tiles: *mut u32) -> i32 {
+    if out_tiles.is_null() {
+        return SUPERZUB_NULL_POINTER;
+    }
+
+    let tiles = unpack(scramble(moves, seed));
+    core::slice::from_raw_parts_mut(out_tiles, 9).copy_from_slice(&tiles);
+
+    SUPERZUB_OK
+}
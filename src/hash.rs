@@ -0,0 +1,59 @@
+// Hash maps and sets for the searches' visited states, keyed by packed fields.
+//
+// By default they use the std hasher. SipHash resists collision attacks, which no puzzle can mount,
+// at a cost that dominates hashing a single u32, so the `fxhash` feature swaps in the multiply-and-rotate
+// hash from Firefox and rustc instead.
+
+#[cfg(feature = "fxhash")]
+pub(crate) type BuildHasher = core::hash::BuildHasherDefault<FxHasher>;
+#[cfg(not(feature = "fxhash"))]
+pub(crate) type BuildHasher = std::collections::hash_map::RandomState;
+
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasher>;
+pub(crate) type HashSet<T> = std::collections::HashSet<T, BuildHasher>;
+
+#[cfg(feature = "fxhash")]
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct FxHasher {
+    hash: u64,
+}
+
+#[cfg(feature = "fxhash")]
+impl FxHasher {
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+#[cfg(feature = "fxhash")]
+impl core::hash::Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
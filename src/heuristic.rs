@@ -0,0 +1,150 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{get_blank_pos, get_tile, Trace};
+
+/// Estimate of the number of moves left to get from `field` to `goal`, used to order informed searches.
+/// A* and IDA* only return optimal traces if the estimate never exceeds the actual number of moves.
+pub trait Heuristic {
+    fn estimate(&self, field: u32, goal: u32) -> u32;
+}
+
+/// Sum of distances each tile has to travel along the grid to get to its place in `goal`.
+/// Every move shifts a single tile by one cell, so it never overestimates the number of moves left.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ManhattanDistance;
+
+/// Number of tiles out of their place in `goal`. Each of them needs at least one move.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MisplacedTiles;
+
+/// Manhattan distance plus 2 moves for every tile that has to leave its goal row or column
+/// to let other tiles of that line pass it.
+///
+/// Two tiles in their goal line, but in reverse order, can't get past each other without
+/// one of them stepping aside and back, which Manhattan distance doesn't account for.
+/// Charging every such pair would overestimate when one tile blocks several others,
+/// so per line it counts the fewest tiles to take out so that the rest are in order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearConflict;
+
+/// No information at all, which degenerates A* to BFS.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Zero;
+
+// cell of every tile in `goal`
+fn goal_positions(goal: u32) -> [u32; 8] {
+    let mut goal_pos = [0; 8];
+    let goal_blank = get_blank_pos(goal);
+    for i in (0..9).filter(|&i| i != goal_blank) {
+        goal_pos[get_tile(goal, i) as usize] = i;
+    }
+    goal_pos
+}
+
+/// Sum of the grid distances between the cells of every tile in `field` and in `goal`, the blank excluded.
+pub fn manhattan(field: u32, goal: u32) -> u32 {
+    let goal_pos = goal_positions(goal);
+
+    let blank = get_blank_pos(field);
+    (0..9)
+        .filter(|&i| i != blank)
+        .map(|i| {
+            let target = goal_pos[get_tile(field, i) as usize];
+            (i / 3).abs_diff(target / 3) + (i % 3).abs_diff(target % 3)
+        })
+        .sum()
+}
+
+impl Heuristic for ManhattanDistance {
+    fn estimate(&self, field: u32, goal: u32) -> u32 {
+        manhattan(field, goal)
+    }
+}
+
+impl Heuristic for LinearConflict {
+    fn estimate(&self, field: u32, goal: u32) -> u32 {
+        let goal_pos = goal_positions(goal);
+        let blank = get_blank_pos(field);
+
+        // for every row and column, where the tiles that belong to it have to go in order of their current cells
+        let line = |cells: [u32; 3], same_line: fn(u32, u32) -> bool, offset: fn(u32) -> u32| {
+            let targets = cells
+                .iter()
+                .filter(|&&i| i != blank)
+                .map(|&i| goal_pos[get_tile(field, i) as usize])
+                .filter(|&target| same_line(cells[0], target))
+                .map(offset)
+                .collect::<Vec<_>>();
+
+            2 * (targets.len() - longest_increasing(&targets)) as u32
+        };
+
+        let rows = (0..3)
+            .map(|r| {
+                line([r * 3, r * 3 + 1, r * 3 + 2], |a, b| a / 3 == b / 3, |target| target % 3)
+            })
+            .sum::<u32>();
+        let columns = (0..3)
+            .map(|c| line([c, c + 3, c + 6], |a, b| a % 3 == b % 3, |target| target / 3))
+            .sum::<u32>();
+
+        manhattan(field, goal) + rows + columns
+    }
+}
+
+fn longest_increasing(xs: &[u32]) -> usize {
+    // lengths of the longest increasing subsequences ending at each element, a line is at most 3 long
+    let mut lengths = vec![1; xs.len()];
+    for i in 0..xs.len() {
+        for j in 0..i {
+            if xs[j] < xs[i] {
+                lengths[i] = lengths[i].max(lengths[j] + 1);
+            }
+        }
+    }
+    lengths.into_iter().max().unwrap_or(0)
+}
+
+impl Heuristic for MisplacedTiles {
+    fn estimate(&self, field: u32, goal: u32) -> u32 {
+        let (blank, goal_blank) = (get_blank_pos(field), get_blank_pos(goal));
+        (0..9)
+            .filter(|&i| i != blank && (i == goal_blank || get_tile(field, i) != get_tile(goal, i)))
+            .count() as u32
+    }
+}
+
+impl Heuristic for Zero {
+    fn estimate(&self, _: u32, _: u32) -> u32 {
+        0
+    }
+}
+
+impl<H: Heuristic + ?Sized> Heuristic for &H {
+    fn estimate(&self, field: u32, goal: u32) -> u32 {
+        (**self).estimate(field, goal)
+    }
+}
+
+/// The trace like its `Display`, with every board followed by the moves made so far (g), the estimate
+/// of the moves left to `goal` (h) and their sum (f), as A* would score the state.
+///
+/// Along an optimal trace of a consistent heuristic f never decreases, and h never exceeds the actual
+/// number of moves left, which is the trace length minus g.
+pub fn display_annotated(trace: &Trace, heuristic: &dyn Heuristic, goal: u32) -> String {
+    let mut out = String::new();
+
+    for (g, (field, board)) in trace.states().zip(trace.boards()).enumerate() {
+        if g > 0 {
+            out.push_str("\n\n");
+        }
+
+        let h = heuristic.estimate(field, goal);
+        write!(out, "{}\ng = {}, h = {}, f = {}", board, g, h, g as u32 + h).unwrap();
+    }
+
+    out
+}
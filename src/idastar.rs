@@ -0,0 +1,87 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{check_solvability, down, left, right, unpack, up, Heuristic, Trace};
+
+enum Outcome {
+    Found,
+    // the smallest f-cost that exceeded the cutoff
+    Exceeded(u32),
+    Exhausted,
+}
+
+/// Iterative deepening A*: depth-first search that prunes states whose f-cost exceeds the cutoff,
+/// raising the cutoff to the smallest pruned f-cost after every iteration.
+/// Only the current path is kept in memory, at the price of revisiting states.
+///
+/// Returns `None` if the output isn't reachable, i.e. the parity of inversions differs. That is checked
+/// up front, as the search itself only avoids cycles along its current path and would never run out of states.
+pub fn idastar(input: u32, output: u32, heuristic: impl Heuristic) -> Option<Trace> {
+    check_solvability(&unpack(input), &unpack(output)).ok()?;
+
+    let estimate = |field| heuristic.estimate(field, output);
+    ida(input, output, &[up, down, left, right], estimate).map(Trace::new)
+}
+
+pub(crate) fn ida<S: Copy + Eq>(
+    input: S,
+    output: S,
+    moves: &[fn(S) -> S],
+    heuristic: impl Fn(S) -> u32,
+) -> Option<Vec<S>> {
+    let mut path = vec![input];
+    let mut cutoff = heuristic(input);
+
+    loop {
+        match search(&mut path, 0, cutoff, output, moves, &heuristic) {
+            Outcome::Found => return Some(path),
+            Outcome::Exceeded(f) => cutoff = f,
+            Outcome::Exhausted => return None,
+        }
+    }
+}
+
+fn search<S: Copy + Eq>(
+    path: &mut Vec<S>,
+    cost: u32,
+    cutoff: u32,
+    output: S,
+    moves: &[fn(S) -> S],
+    heuristic: &impl Fn(S) -> u32,
+) -> Outcome {
+    let current = *path.last().unwrap();
+
+    let f = cost + heuristic(current);
+    if f > cutoff {
+        return Outcome::Exceeded(f);
+    }
+
+    if current == output {
+        return Outcome::Found;
+    }
+
+    let mut next_cutoff = None;
+
+    for f in moves {
+        let value = f(current);
+
+        // skips both blocked moves and cycles
+        if path.contains(&value) {
+            continue;
+        }
+
+        path.push(value);
+
+        match search(path, cost + 1, cutoff, output, moves, heuristic) {
+            Outcome::Found => return Outcome::Found,
+            Outcome::Exceeded(f) => {
+                next_cutoff = Some(next_cutoff.map_or(f, |min: u32| min.min(f)))
+            }
+            Outcome::Exhausted => {}
+        }
+
+        path.pop();
+    }
+
+    next_cutoff.map_or(Outcome::Exhausted, Outcome::Exceeded)
+}
@@ -0,0 +1,419 @@
+// Minimal JSON support for persisting states and traces without pulling in serde.
+//
+// A `State` is written as an array of its 9 tile values in reading order, 0 being the blank tile,
+// and a `Trace` as an object holding its states from the input to the goal:
+//
+// {"trace":[[1,2,3,4,5,0,6,7,8],[1,2,3,4,5,8,6,7,0],...]}
+//
+// along with an "alphabet" array of the 9 symbols to draw the tiles with, if the trace has one.
+//
+// The tiles of a puzzle to solve come in the same array as a state, see `TryFrom<&Value> for [u32; 9]`.
+
+use std::convert::{TryFrom, TryInto};
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::{pack, validate_input, verify, SolveError, State, Trace};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn parse(s: &str) -> Result<Value, SolveError> {
+        let mut parser = Parser { input: s.as_bytes(), pos: 0, depth: 0 };
+
+        let value = parser.value()?;
+        parser.whitespace();
+
+        match parser.input.get(parser.pos) {
+            None => Ok(value),
+            Some(_) => Err(parser.error("trailing characters")),
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        match *self {
+            Value::Number(x) if x.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(&x) => {
+                Some(x as u32)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(x) => write!(f, "{}", x),
+            Value::String(s) => write_string(f, s),
+            Value::Array(values) => write!(f, "[{}]", values.iter().format(",")),
+            Value::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    // arrays and objects the parser is inside of, as each of them takes a recursive call
+    depth: usize,
+}
+
+impl Parser<'_> {
+    // far more than a state or a trace needs, and far less than it takes to overflow the stack
+    const MAX_DEPTH: usize = 128;
+
+    fn error(&self, message: &str) -> SolveError {
+        SolveError::InvalidJson(format!("{} at byte {}", message, self.pos))
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), SolveError> {
+        if self.input[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", literal)))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, SolveError> {
+        self.whitespace();
+
+        match self.input.get(self.pos) {
+            Some(b'n') => self.expect("null").map(|_| Value::Null),
+            Some(b't') => self.expect("true").map(|_| Value::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let values = self.nested(|parser| parser.list(b']', Self::value))?;
+                Ok(Value::Array(values))
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let fields = self.nested(|parser| {
+                    parser.list(b'}', |parser| {
+                        parser.whitespace();
+                        let key = parser.string()?;
+                        parser.whitespace();
+                        parser.expect(":")?;
+                        Ok((key, parser.value()?))
+                    })
+                })?;
+                Ok(Value::Object(fields))
+            }
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    // runs `parse` one array or object deeper, refusing to nest past MAX_DEPTH
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, SolveError>,
+    ) -> Result<T, SolveError> {
+        if self.depth == Self::MAX_DEPTH {
+            return Err(self.error("too deeply nested"));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    // comma separated items up to the closing bracket, the opening one being already consumed
+    fn list<T>(
+        &mut self,
+        close: u8,
+        mut item: impl FnMut(&mut Self) -> Result<T, SolveError>,
+    ) -> Result<Vec<T>, SolveError> {
+        let mut items = Vec::new();
+
+        self.whitespace();
+        if self.input.get(self.pos) == Some(&close) {
+            self.pos += 1;
+            return Ok(items);
+        }
+
+        loop {
+            items.push(item(self)?);
+            self.whitespace();
+
+            match self.input.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(&c) if c == close => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                _ => return Err(self.error("expected `,` or a closing bracket")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, SolveError> {
+        let start = self.pos;
+
+        // -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?, which f64::from_str is more lenient than
+        self.skip(b"-");
+        if !self.skip(b"0") && self.digits() == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.skip(b".") && self.digits() == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.skip(b"eE") {
+            self.skip(b"+-");
+            if self.digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+
+        std::str::from_utf8(&self.input[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Value::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    // skips a single byte if it is one of `bytes`
+    fn skip(&mut self, bytes: &[u8]) -> bool {
+        let found = self.input.get(self.pos).is_some_and(|b| bytes.contains(b));
+        self.pos += found as usize;
+        found
+    }
+
+    // skips a run of digits, returning how many there were
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn string(&mut self) -> Result<String, SolveError> {
+        self.expect("\"")?;
+
+        let mut bytes = Vec::new();
+        loop {
+            match self.input.get(self.pos) {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.input.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                Some(&b) => bytes.push(b),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8"))
+    }
+
+    // reads the digits of `\uXXXX` (and of the low surrogate following a high one),
+    // leaving the position on the last digit
+    fn unicode_escape(&mut self) -> Result<char, SolveError> {
+        let high = self.hex(self.pos + 1)?;
+        self.pos += 4;
+
+        let code = if (0xD800..0xDC00).contains(&high) {
+            let low = match self.input.get(self.pos + 1..self.pos + 3) {
+                Some(b"\\u") => self.hex(self.pos + 3)?,
+                _ => return Err(self.error("unpaired surrogate")),
+            };
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 6;
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        std::char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex(&self, start: usize) -> Result<u32, SolveError> {
+        self.input
+            .get(start..start + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+pub trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self, SolveError>;
+}
+
+pub fn to_string<T: ToJson>(value: &T) -> String {
+    value.to_json().to_string()
+}
+
+pub fn from_str<T: FromJson>(s: &str) -> Result<T, SolveError> {
+    T::from_json(&Value::parse(s)?)
+}
+
+impl ToJson for State {
+    fn to_json(&self) -> Value {
+        Value::Array(self.tiles().iter().map(|&tile| Value::Number(tile as f64)).collect())
+    }
+}
+
+impl TryFrom<&Value> for [u32; 9] {
+    type Error = SolveError;
+
+    /// Tiles of a puzzle written as an array of 9 numbers in reading order, 0 being the blank tile,
+    /// e.g. from a request body, checked to be a permutation of 0..=8 like `solve` does.
+    fn try_from(value: &Value) -> Result<Self, SolveError> {
+        let values = value.as_array().ok_or_else(|| invalid("a state must be an array"))?;
+
+        let tiles: Vec<u32> = values
+            .iter()
+            .map(|value| value.as_u32().ok_or_else(|| invalid("a tile must be a number")))
+            .try_collect()?;
+        let tiles: [u32; 9] =
+            tiles.as_slice().try_into().map_err(|_| SolveError::WrongTileCount(tiles.len()))?;
+
+        validate_input(&tiles)?;
+        Ok(tiles)
+    }
+}
+
+impl FromJson for State {
+    fn from_json(value: &Value) -> Result<Self, SolveError> {
+        let tiles: [u32; 9] = value.try_into()?;
+        Ok(State(pack(&tiles)))
+    }
+}
+
+impl ToJson for Trace {
+    fn to_json(&self) -> Value {
+        let states = self.states().map(|field| State(field).to_json()).collect();
+        let mut fields = vec![("trace".to_owned(), Value::Array(states))];
+
+        if let Some(alphabet) = &self.alphabet {
+            let symbols = alphabet.iter().cloned().map(Value::String).collect();
+            fields.push(("alphabet".to_owned(), Value::Array(symbols)));
+        }
+
+        Value::Object(fields)
+    }
+}
+
+impl FromJson for Trace {
+    fn from_json(value: &Value) -> Result<Self, SolveError> {
+        let states = value
+            .get("trace")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid("a trace must be an object with a `trace` array"))?;
+
+        let trace =
+            states.iter().map(|state| State::from_json(state).map(u32::from)).try_collect()?;
+
+        let alphabet = match value.get("alphabet") {
+            None => None,
+            Some(symbols) => Some(alphabet(symbols)?),
+        };
+
+        // states that don't follow each other would break `Trace::directions`
+        let trace = Trace { trace, alphabet };
+        verify(&trace)?;
+        Ok(trace)
+    }
+}
+
+// symbols a trace is drawn with, one string for each of the 9 tile values
+fn alphabet(value: &Value) -> Result<Vec<String>, SolveError> {
+    let symbols = match value.as_array() {
+        Some(symbols) if symbols.len() == 9 => symbols,
+        _ => return Err(invalid("an alphabet must be an array of 9 strings")),
+    };
+
+    symbols
+        .iter()
+        .map(|symbol| match symbol {
+            Value::String(symbol) => Ok(symbol.clone()),
+            _ => Err(invalid("an alphabet must be an array of 9 strings")),
+        })
+        .collect()
+}
+
+fn invalid(message: &str) -> SolveError {
+    SolveError::InvalidJson(message.to_owned())
+}
@@ -0,0 +1,1232 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
+#[cfg(feature = "hashmap")]
+use hash::HashMap;
+
+use itertools::Itertools;
+
+#[macro_use]
+mod log;
+
+#[cfg(feature = "std")]
+mod animate;
+#[cfg(feature = "std")]
+mod astar;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod bidirectional;
+pub mod board;
+#[cfg(feature = "std")]
+mod distance;
+pub mod factoriadic;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod hash;
+mod heuristic;
+mod idastar;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod pdb;
+mod random;
+mod state;
+mod svg;
+mod symmetry;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub use animate::{animate, solve_interactive};
+#[cfg(feature = "std")]
+pub use astar::{astar, astar_stats, astar_weighted};
+#[cfg(feature = "std")]
+pub use batch::{batch_stats, solve_batch, solve_with_table, BatchStats};
+#[cfg(feature = "std")]
+pub use bidirectional::bidirectional_bfs;
+pub use board::{solvable_count, Board};
+#[cfg(feature = "parallel")]
+pub use distance::distance_table_parallel;
+#[cfg(feature = "std")]
+pub use distance::{
+    bfs_levels, bfs_levels_limited, distance, distance_histogram, distance_table,
+    distance_table_canonical, distance_table_with_progress, hardest_states, load_table, save_table,
+    shuffle_to_distance,
+};
+pub use heuristic::{
+    display_annotated, manhattan, Heuristic, LinearConflict, ManhattanDistance, MisplacedTiles,
+    Zero,
+};
+pub use idastar::idastar;
+pub use random::{random_solvable, scramble};
+use state::Framed;
+pub use state::State;
+pub use svg::to_svg;
+pub use symmetry::{
+    anti_transpose, canonical, reflect_horizontal, reflect_vertical, rotate180, rotate270,
+    rotate90, symmetries, transpose, transpose_relabeled,
+};
+
+// This program solves a variation of 15-puzzle game.
+//
+// Let's assume we have a 3 by 3 field like this:
+//
+// +---+---+---+
+// | a | b | c |
+// | d | e |   |
+// | g | h | i |
+// +---+---+---+
+//
+// Note that element on (x: 2, y: 1) represents a blank tile.
+// This will be converted to an unsigned 32 bit integer by placing nine 3-bit tile indices [0, 7], occupying 27 bits.
+// 5 bits left will be used as a position(P) of a blank tile, so that it can be found quickly.
+// The field above will be converted to in binary: PPPPPIII.HHHGGG00.0EEEDDDC.CCBBBAAA,
+// where dots delimit bytes and 3 bits owned by the blank tile are filled with zeros.
+
+/// The standard goal, packed. Tiles 1 to 8 in reading order with the blank tile in the bottom right corner:
+///
+/// ```text
+/// +---+---+---+
+/// | 1 | 2 | 3 |
+/// | 4 | 5 | 6 |
+/// | 7 | 8 |   |
+/// +---+---+---+
+/// ```
+pub const GOAL: u32 = 0b01000000111110101100011010001000;
+
+/// Tiles of `GOAL` in reading order, as `pack` takes them.
+pub const fn goal_array() -> [u32; 9] {
+    [1, 2, 3, 4, 5, 6, 7, 8, 0]
+}
+
+/// Whether the field is the standard goal, e.g. to tell when a player has finished.
+pub const fn is_solved(field: u32) -> bool {
+    field == GOAL
+}
+
+/// Whether the field is the packed `goal`. Packing is unique, so equal fields are equal boards.
+pub const fn is_solved_to(field: u32, goal: u32) -> bool {
+    field == goal
+}
+
+/// The goal some sources use instead of `GOAL`, packed: the blank tile in the top left corner followed by tiles 1 to 8.
+/// Pass `unpack(goal_blank_first())` to `solve_to` to solve towards it.
+///
+/// The tiles are in the same order as in `GOAL`, so exactly the same puzzles can reach either of them.
+pub fn goal_blank_first() -> u32 {
+    pack(&goal_with_blank(0))
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    /// Symbols of the input aren't a permutation of the alphabet.
+    AlphabetMismatch {
+        /// Values that don't appear in the input.
+        missing: Vec<String>,
+        /// Values that appear more than once or aren't expected at all.
+        extra: Vec<String>,
+    },
+    /// The parity of inversions differs from the goal's, so no sequence of moves reaches it.
+    /// Swapping any two tiles other than the blank flips the parity and makes the puzzle solvable.
+    Unsolvable {
+        /// Pairs of tiles where the greater one comes first in reading order, ignoring the blank tile.
+        inversions: usize,
+    },
+    /// A token that is neither a number nor `_`.
+    InvalidToken(String),
+    /// Number of values found in the input, other than the number of cells on the board.
+    WrongTileCount(usize),
+    /// No tile is 0, the blank one.
+    MissingBlank,
+    /// More than one tile is 0, the blank one.
+    DuplicateBlank,
+    /// A tile other than the blank one appears more than once.
+    DuplicateTile(u32),
+    /// A tile greater than the number of cells on the board minus one.
+    OutOfRange(u32),
+    /// The search expanded more states than it was allowed to.
+    LimitExceeded,
+    /// A distance table misses a state on the way to the goal, or has no neighbor of it a move closer.
+    IncompleteTable,
+    /// No goals were given to solve towards.
+    NoGoals,
+    /// State at this index of a trace, or of the outputs given to a search, isn't a validly packed field.
+    CorruptState(usize),
+    /// State at this index of a trace isn't a single move away from the previous one.
+    IllegalMove(usize),
+    /// Input that isn't valid JSON or doesn't describe what was asked for. Only produced with the `json` feature,
+    /// but always there, so that turning the feature on doesn't break exhaustive matches elsewhere.
+    InvalidJson(String),
+}
+
+impl core::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SolveError::AlphabetMismatch { missing, extra } => {
+                write!(f, "input is not a valid permutation of the tiles")?;
+                for (name, values) in [("missing", missing), ("unexpected", extra)] {
+                    if let Some((first, rest)) = values.split_first() {
+                        write!(f, ", {}: {}", name, first)?;
+                        for value in rest {
+                            write!(f, " {}", value)?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            SolveError::Unsolvable { inversions } => write!(
+                f,
+                "puzzle is unsolvable: {} inversions don't match the parity of the goal",
+                inversions
+            ),
+            SolveError::InvalidToken(token) => {
+                write!(f, "invalid token `{}`, expected a number or `_`", token)
+            }
+            SolveError::WrongTileCount(count) => {
+                write!(f, "found {} tiles, which don't fill the board", count)
+            }
+            SolveError::MissingBlank => write!(f, "there is no blank tile 0"),
+            SolveError::DuplicateBlank => write!(f, "there is more than one blank tile 0"),
+            SolveError::DuplicateTile(tile) => write!(f, "tile {} appears more than once", tile),
+            SolveError::OutOfRange(tile) => write!(f, "tile {} doesn't fit on the board", tile),
+            SolveError::LimitExceeded => write!(f, "search expanded more states than allowed"),
+            SolveError::IncompleteTable => {
+                write!(f, "distance table has no way to the goal from a state on the way")
+            }
+            SolveError::NoGoals => write!(f, "there are no goals to solve towards"),
+            SolveError::CorruptState(index) => {
+                write!(f, "state {} of the trace is not a valid field", index)
+            }
+            SolveError::IllegalMove(index) => {
+                write!(
+                    f,
+                    "state {} of the trace is not a single move away from the previous one",
+                    index
+                )
+            }
+            SolveError::InvalidJson(message) => write!(f, "invalid JSON: {}", message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SolveError {}
+
+/// Search `solve_with` runs. Non-exhaustive, as the variants depend on the features: turning `std` on
+/// somewhere else in the dependency graph must not break a match written without it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Algorithm {
+    #[default]
+    Bfs,
+    /// Needs a hash map, so it's only available with the `std` feature.
+    #[cfg(feature = "std")]
+    AStar,
+    IdaStar,
+}
+
+/// Options for `solve_with`. The default solves towards the standard goal with BFS, without a limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveConfig {
+    pub algorithm: Algorithm,
+    /// Give up with `SolveError::LimitExceeded` after expanding this many states.
+    /// IDA* only keeps its current path in memory, so it isn't limited.
+    pub max_nodes: Option<usize>,
+    /// Arrangement of the tiles to solve towards instead of the standard goal.
+    pub goal: Option<[u32; 9]>,
+    /// Number of states BFS reserves room for up front in its queue, and in its hash map with the `hashmap`
+    /// feature, defaulting to `BFS_CAPACITY`. A smaller one saves memory on easy puzzles, which the search
+    /// has to pay for by growing the containers on hard ones, see `SearchStats::reallocations`.
+    pub capacity: Option<usize>,
+    /// Have BFS keep only one state of every pair that `canonical` maps together, roughly halving the states
+    /// it queues and, with the `hashmap` feature, stores. Only takes effect if the goal maps onto itself,
+    /// like the standard one does, as the two states of a pair are then equally far from it.
+    pub canonical: bool,
+    /// Print the packed input and goal, and how much work the search took, to stderr.
+    /// Only has an effect with the `std` feature.
+    pub verbose: bool,
+}
+
+/// Direction in which the blank tile moves, i.e. opposite to the tile that slides into its place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// The direction undoing a move in this one.
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+impl core::fmt::Display for Direction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let letter = match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        f.write_str(letter)
+    }
+}
+
+/// States of a solution in the order a player goes through them: the input first and the goal last.
+///
+/// BFS searches from the goal towards the input, but every parent it records is a move closer to the goal,
+/// so walking the parents from the input already lists the states forwards. The other searches
+/// walk their parents back from the goal and reverse the result, so all of them return the same orientation.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Trace {
+    // According to Wiki, the longest optimal solution is 31 moves long.
+    trace: Vec<u32>,
+    // symbols to draw the tiles with, indexed by tile value
+    alphabet: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// States taken off the frontier to generate their neighbors.
+    pub expanded: usize,
+    /// New states discovered and put onto the frontier.
+    pub generated: usize,
+    /// Largest number of states waiting on the frontier at once.
+    pub peak_frontier: usize,
+    /// Times BFS had to grow its queue or hash map beyond the room reserved for them. Always 0 for the other searches.
+    pub reallocations: usize,
+}
+
+/// Default `SolveConfig::capacity`, the number of states reachable from any given one of the 3 by 3 board.
+/// No search visits more, so BFS never has to grow its containers, at the cost of reserving room for all of them
+/// even when the input is a few moves from the goal.
+///
+/// Without the `hashmap` feature the visited states live in a table indexed by rank, which always covers all 9! of them.
+pub const BFS_CAPACITY: usize = fact(9) / 2;
+
+/// Snapshot of a running search, passed to progress callbacks every `PROGRESS_INTERVAL` expanded states.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchProgress {
+    /// Number of moves between the state being expanded and the one the search started from.
+    pub depth: usize,
+    /// States taken off the frontier so far.
+    pub expanded: usize,
+    /// States waiting on the frontier.
+    pub frontier: usize,
+}
+
+pub const PROGRESS_INTERVAL: usize = 10_000;
+
+impl Trace {
+    pub(crate) fn new(trace: Vec<u32>) -> Self {
+        Trace { trace, alphabet: None }
+    }
+
+    /// Trace of the given states, e.g. solutions of consecutive parts glued together.
+    /// Nothing is checked, see `verify` for that, and `compress` to drop the repeated states where the parts meet.
+    /// Until then `directions` panics on the trace.
+    pub fn from_states(states: Vec<u32>) -> Self {
+        Trace::new(states)
+    }
+
+    /// Draws the boards with `alphabet[i]` in place of tile value i, `alphabet[0]` standing for the blank tile.
+    pub fn with_alphabet(mut self, alphabet: &[impl core::fmt::Display; 9]) -> Self {
+        self.alphabet = Some(alphabet.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    pub(crate) fn boards(&self) -> impl Iterator<Item = Framed<'_>> {
+        self.states().map(move |field| Framed { field, alphabet: self.alphabet.as_deref() })
+    }
+
+    /// Number of board states, including the input and the goal.
+    pub fn len(&self) -> usize {
+        self.trace.len()
+    }
+
+    /// Number of moves from the input to the goal, one less than the number of states.
+    pub fn moves_count(&self) -> usize {
+        self.trace.len().saturating_sub(1)
+    }
+
+    /// Whether there are no states at all, which no search returns, but a deserialized trace can be.
+    pub fn is_empty(&self) -> bool {
+        self.trace.is_empty()
+    }
+
+    /// Board states from the input to the goal.
+    pub fn states(&self) -> impl Iterator<Item = u32> + '_ {
+        self.trace.iter().copied()
+    }
+
+    /// Same trace without steps that go nowhere, i.e. a state repeated right away, and without moves
+    /// undone by the next one, e.g. up followed by down. It still starts and ends with the same states.
+    ///
+    /// Cancelling a pair can make another pair adjacent, which gets cancelled too, so up, up, down, down
+    /// leaves the starting state alone. Loops longer than two moves are kept.
+    pub fn compress(&self) -> Trace {
+        let mut states: Vec<u32> = Vec::with_capacity(self.trace.len());
+
+        for &field in &self.trace {
+            let len = states.len();
+            if states.last() == Some(&field) {
+                continue;
+            }
+            if len >= 2 && states[len - 2] == field {
+                states.pop();
+                continue;
+            }
+            states.push(field);
+        }
+
+        Trace { trace: states, alphabet: self.alphabet.clone() }
+    }
+
+    /// Same states from the last one to the first, i.e. from the goal back to the input for a trace
+    /// a search returned, e.g. to rewind a solution. Reversing twice gives the original trace.
+    pub fn reversed(&self) -> Trace {
+        let trace = self.trace.iter().rev().copied().collect();
+        Trace { trace, alphabet: self.alphabet.clone() }
+    }
+
+    /// Moves of the blank tile between consecutive states, from the input forwards, so replaying them
+    /// on the input with `replay` ends at the goal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state isn't a single move away from the previous one, which can only happen for a trace
+    /// built with `from_states`. See `try_directions` for such traces.
+    pub fn directions(&self) -> Vec<Direction> {
+        self.try_directions().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as `directions`, but returns `SolveError::IllegalMove` with the index of the first state
+    /// that isn't a single move away from the previous one.
+    pub fn try_directions(&self) -> Result<Vec<Direction>, SolveError> {
+        self.trace
+            .iter()
+            .tuple_windows()
+            .enumerate()
+            .map(|(index, (&from, &to))| {
+                neighbors(from)
+                    .find(|&(_, value)| value == to)
+                    .map(|(direction, _)| direction)
+                    .ok_or(SolveError::IllegalMove(index + 1))
+            })
+            .collect()
+    }
+
+    /// Directions in which a player slides the tiles to get from the input to the goal. Each tile moves
+    /// into the blank cell, so it goes the opposite way from the blank tile in `directions`.
+    pub fn tile_moves(&self) -> Vec<Direction> {
+        self.directions().into_iter().map(Direction::opposite).collect()
+    }
+
+    /// The solution as `{"moves":["U",...],"states":[[1,2,3,4,5,0,6,7,8],...]}`, the states as tiles in
+    /// reading order, which is what both `--format json` and `wasm::solve_json` print.
+    pub fn solution_json(&self) -> String {
+        let moves = self
+            .directions()
+            .iter()
+            .map(|direction| format!("\"{}\"", direction))
+            .collect::<Vec<_>>();
+        let states = self
+            .states()
+            .map(|field| {
+                let tiles = unpack(field).iter().map(ToString::to_string).collect::<Vec<_>>();
+                format!("[{}]", tiles.join(","))
+            })
+            .collect::<Vec<_>>();
+
+        format!("{{\"moves\":[{}],\"states\":[{}]}}", moves.join(","), states.join(","))
+    }
+}
+
+impl core::fmt::Display for Trace {
+    /// Framed boards separated by blank lines, or with `{:#}` bare rows of tile indices,
+    /// each board followed by a blank line.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            return self.trace.iter().try_for_each(|&field| writeln!(f, "{:#}", State(field)));
+        }
+
+        write!(f, "{}", self.boards().format("\n\n"))
+    }
+}
+
+#[inline(always)]
+pub const fn get_blank_pos(field: u32) -> u32 {
+    field >> 27
+}
+
+#[inline(always)]
+pub const fn get_tile(field: u32, i: u32) -> u32 {
+    let mask = get_mask(i);
+    (field & mask) >> (i * 3)
+}
+
+/// Row and column of the blank tile, counted from the top left corner.
+pub const fn blank_coords(field: u32) -> (u32, u32) {
+    let pos = get_blank_pos(field);
+    (pos / 3, pos % 3)
+}
+
+/// Row and column of the tile with the given value, 0 being the blank tile.
+///
+/// Panics if the value isn't on the board.
+pub fn tile_coords(field: u32, tile: u32) -> (u32, u32) {
+    if tile == 0 {
+        return blank_coords(field);
+    }
+
+    let blank = get_blank_pos(field);
+    let pos = (0..9)
+        .find(|&i| i != blank && get_tile(field, i) == tile - 1)
+        .unwrap_or_else(|| panic!("tile {} isn't on the board", tile));
+    (pos / 3, pos % 3)
+}
+
+#[inline(always)]
+const fn to_pos(x: u32) -> u32 {
+    x << 27
+}
+
+#[inline(always)]
+const fn get_mask(x: u32) -> u32 {
+    0b111 << (x * 3)
+}
+
+// Precomputed bit operations moving the blank in each direction (up, down, left, right) from every cell:
+// `mask` selects the tile that slides into the blank's place, rotating by `shift` moves it there,
+// and adding `delta` updates the blank's position. Moves into a wall are all zeros and keep the field as is.
+#[derive(Clone, Copy)]
+struct Move {
+    mask: u32,
+    shift: u32,
+    delta: u32,
+}
+
+// sized for every value the 5 position bits can hold, so indexing needs no bounds check
+const MOVES: [[Move; 4]; 32] = move_table();
+
+const fn move_table() -> [[Move; 4]; 32] {
+    const WALL: Move = Move { mask: 0, shift: 0, delta: 0 };
+    const DELTAS: [i32; 4] = [-3, 3, -1, 1];
+
+    let mut table = [[WALL; 4]; 32];
+
+    let mut pos = 0;
+    while pos < 9 {
+        let in_bounds = [pos >= 3, pos <= 5, pos % 3 != 0, pos % 3 != 2];
+
+        let mut direction = 0;
+        while direction < 4 {
+            if in_bounds[direction] {
+                let delta = DELTAS[direction];
+                let target = (pos as i32 + delta) as u32;
+
+                table[pos][direction] = Move {
+                    mask: get_mask(target),
+                    // negative shifts wrap around, so that rotating right by 32 - 3 rotates left by 3
+                    shift: ((32 + delta * 3) % 32) as u32,
+                    delta: to_pos(delta as u32),
+                };
+            }
+            direction += 1;
+        }
+        pos += 1;
+    }
+
+    table
+}
+
+#[inline(always)]
+fn make_move(field: u32, direction: usize) -> u32 {
+    let Move { mask, shift, delta } = MOVES[get_blank_pos(field) as usize][direction];
+
+    // the moved tile leaves zeros behind and lands on the blank's cell, which holds zeros as well
+    let moved = field & mask;
+    (field ^ moved | moved.rotate_right(shift)).wrapping_add(delta)
+}
+
+pub fn up(field: u32) -> u32 {
+    make_move(field, 0)
+}
+
+pub fn down(field: u32) -> u32 {
+    make_move(field, 1)
+}
+
+pub fn left(field: u32) -> u32 {
+    make_move(field, 2)
+}
+
+pub fn right(field: u32) -> u32 {
+    make_move(field, 3)
+}
+
+/// Moves the blank tile in the given direction, same as calling `up`, `down`, `left` or `right`.
+pub fn apply(field: u32, direction: Direction) -> u32 {
+    // the variants are declared in the order of the move table
+    make_move(field, direction as usize)
+}
+
+/// States a single move away from `field`, paired with the direction the blank tile moved in.
+/// Moves into a wall, which leave the field as it is, are skipped.
+pub fn neighbors(field: u32) -> impl Iterator<Item = (Direction, u32)> {
+    [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+        .iter()
+        .map(move |&direction| (direction, apply(field, direction)))
+        .filter(move |&(_, value)| value != field)
+}
+
+// Only for the sizes of the 3x3 board: 21! and up overflow a 64-bit usize, and 13! a 32-bit one.
+// Larger boards go through `factoriadic::checked_fact`.
+const fn fact(mut x: usize) -> usize {
+    let mut ret = 1;
+    while x > 1 {
+        ret *= x;
+        x -= 1;
+    }
+    ret
+}
+
+/// https://www.cs.bham.ac.uk/~mdr/teaching/modules04/java2/TilesSolvability.html
+///
+/// If N(in NxN puzzle) is odd, then puzzle instance is solvable if number of inversions is even in the input state.
+/// An inversion is when a tile precedes another tile with a lower number on it.
+///
+/// Proof:
+///
+/// Moving a tile along the row (left or right) doesn’t change the number of inversions,
+/// and therefore doesn’t change its polarity.
+/// Moving a tile along the column (up or down) can change the number of inversions.
+/// The tile moves past an even number of other tiles (N – 1). So move changes number of inversions by (+i - k),
+/// so i and k are both odd or even, so the change is even
+///
+/// The standard goal has no inversions at all. For an arbitrary goal the same argument means
+/// the input is solvable if its number of inversions has the same parity as the goal's.
+fn check_solvability(input: &[u32; 9], goal: &[u32; 9]) -> Result<(), SolveError> {
+    let tiles = |field: &[u32; 9]| field.iter().copied().filter(|&x| x != 0).collect::<Vec<_>>();
+
+    (factoriadic::parity(&tiles(input)) == factoriadic::parity(&tiles(goal)))
+        .then_some(())
+        .ok_or_else(|| SolveError::Unsolvable { inversions: count_inversions(input) })
+}
+
+/// Number of pairs of tiles where the greater one comes first in reading order, ignoring the blank tile.
+pub fn count_inversions(input: &[u32; 9]) -> usize {
+    (0..9)
+        .flat_map(|i| core::iter::once(i).cartesian_product(i + 1..9))
+        .filter(|&(i, k)| input[k] != 0 && input[i] > input[k])
+        .count()
+}
+
+/// Makes a permutation of 0..=8 solvable by swapping the tiles of the first two cells that aren't blank,
+/// which flips the parity of inversions, see `check_solvability`. No puzzle gets solvable with fewer changes.
+///
+/// Returns the cells it swapped, in reading order, or None if the input was solvable and stays unchanged.
+pub fn make_solvable(input: &mut [u32; 9]) -> Option<(usize, usize)> {
+    if check_solvability(input, &goal_array()).is_ok() {
+        return None;
+    }
+
+    let mut tiles = (0..9).filter(|&i| input[i] != 0);
+    let pair = (tiles.next().unwrap(), tiles.next().unwrap());
+    input.swap(pair.0, pair.1);
+
+    Some(pair)
+}
+
+/// Tile values of symbols drawn from `alphabet`, where `alphabet[i]` stands for value i.
+fn translate<T: PartialEq + core::fmt::Display>(
+    input: &[T; 9],
+    alphabet: &[T; 9],
+) -> Result<[u32; 9], SolveError> {
+    check_alphabet(input, alphabet)?;
+
+    let mut tiles = [0; 9];
+    for (tile, symbol) in tiles.iter_mut().zip(input) {
+        *tile = alphabet.iter().position(|x| x == symbol).unwrap() as u32;
+    }
+    Ok(tiles)
+}
+
+fn validate_input(input: &[u32; 9]) -> Result<(), SolveError> {
+    check_tiles(input)
+}
+
+// whether the field packs a permutation of the tiles, with nothing set beyond the blank's position
+pub(crate) fn is_packed(field: u32) -> bool {
+    let tiles = unpack(field);
+    validate_input(&tiles).is_ok() && pack(&tiles) == field
+}
+
+/// Checks that `input` holds every value of 0..input.len() exactly once, reporting the first problem
+/// in this order: a value out of range, no blank tile, more than one blank tile, a duplicated tile.
+pub(crate) fn check_tiles(input: &[u32]) -> Result<(), SolveError> {
+    if let Some(&tile) = input.iter().find(|&&tile| tile as usize >= input.len()) {
+        return Err(SolveError::OutOfRange(tile));
+    }
+
+    match input.iter().filter(|&&tile| tile == 0).count() {
+        0 => return Err(SolveError::MissingBlank),
+        1 => {}
+        _ => return Err(SolveError::DuplicateBlank),
+    }
+
+    match input.iter().enumerate().find(|&(i, tile)| input[..i].contains(tile)) {
+        Some((_, &tile)) => Err(SolveError::DuplicateTile(tile)),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `input` is a permutation of `alphabet`, whose values are assumed to be distinct,
+/// reporting every missing and every duplicated or unexpected value otherwise.
+pub(crate) fn check_alphabet<T: PartialEq + core::fmt::Display>(
+    input: &[T],
+    alphabet: &[T],
+) -> Result<(), SolveError> {
+    let count = |x: &T| input.iter().filter(|&y| x == y).count();
+
+    let missing =
+        alphabet.iter().filter(|x| count(x) == 0).map(ToString::to_string).collect::<Vec<_>>();
+    let extra = input
+        .iter()
+        .enumerate()
+        .filter(|&(i, x)| !input[..i].contains(x) && (count(x) > 1 || !alphabet.contains(x)))
+        .map(|(_, x)| x.to_string())
+        .collect::<Vec<_>>();
+
+    (missing.is_empty() && extra.is_empty())
+        .then_some(())
+        .ok_or(SolveError::AlphabetMismatch { missing, extra })
+}
+
+/// Tiles of a valid input in reading order, 0 being the blank tile. Solvability isn't checked,
+/// as it depends on the goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle([u32; 9]);
+
+impl Puzzle {
+    pub fn tiles(&self) -> &[u32; 9] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u32]> for Puzzle {
+    type Error = SolveError;
+
+    /// Takes exactly 9 values, a permutation of 0..=8.
+    fn try_from(tiles: &[u32]) -> Result<Self, SolveError> {
+        let tiles: [u32; 9] =
+            tiles.try_into().map_err(|_| SolveError::WrongTileCount(tiles.len()))?;
+        validate_input(&tiles)?;
+        Ok(Puzzle(tiles))
+    }
+}
+
+impl core::str::FromStr for Puzzle {
+    type Err = SolveError;
+
+    /// Same format as `parse` takes.
+    fn from_str(s: &str) -> Result<Self, SolveError> {
+        Puzzle::try_from(&parse(s)?[..])
+    }
+}
+
+impl core::fmt::Display for Puzzle {
+    /// Draws the board like `State` does, including the alternate form.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&State::from(&self.0), f)
+    }
+}
+
+/// Parses 9 whitespace separated values in reading order, where either `0` or `_` stands for the blank tile.
+/// Values may span any number of lines, so the input can be laid out like the board itself.
+pub fn parse(s: &str) -> Result<[u32; 9], SolveError> {
+    let tiles: Vec<u32> = s
+        .split_whitespace()
+        .map(|token| match token {
+            "_" => Ok(0),
+            _ => token.parse().map_err(|_| SolveError::InvalidToken(token.to_owned())),
+        })
+        .collect::<Result<_, _>>()?;
+
+    tiles.as_slice().try_into().map_err(|_| SolveError::WrongTileCount(tiles.len()))
+}
+
+/// Compact form of the field for logs and fixtures: its 9 tiles in reading order, `_` being the blank,
+/// e.g. `12345_678`.
+pub fn encode(field: u32) -> String {
+    unpack(field)
+        .iter()
+        .map(|&tile| if tile == 0 { '_' } else { (b'0' + tile as u8) as char })
+        .collect()
+}
+
+/// Inverse of `encode`, also taking `0` for the blank tile.
+pub fn decode(s: &str) -> Result<u32, SolveError> {
+    let tiles = s
+        .chars()
+        .map(|c| match c {
+            '_' => Ok(0),
+            _ => c.to_digit(10).ok_or_else(|| SolveError::InvalidToken(c.to_string())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let puzzle = Puzzle::try_from(tiles.as_slice())?;
+    Ok(pack(puzzle.tiles()))
+}
+
+pub fn pack(input: &[u32; 9]) -> u32 {
+    input.iter().enumerate().fold(0, |packed, (index, &tile)| {
+        packed | if tile == 0 { to_pos(index as u32) } else { (tile - 1) << (index * 3) }
+    })
+}
+
+/// Applies the moves to `start` in order and returns the final state.
+///
+/// A move that would take the blank tile off the board leaves the state unchanged, just like
+/// calling the move function directly, so the rest of the moves are still applied.
+pub fn replay(start: u32, moves: &[Direction]) -> u32 {
+    moves.iter().fold(start, |field, &direction| apply(field, direction))
+}
+
+/// Checks that every state of the trace is a validly packed field and that
+/// each one is reachable from the previous one with a single move.
+pub fn verify(trace: &Trace) -> Result<(), SolveError> {
+    if let Some(index) = trace.trace.iter().position(|&field| !is_packed(field)) {
+        return Err(SolveError::CorruptState(index));
+    }
+
+    for (index, (&from, &to)) in trace.trace.iter().tuple_windows().enumerate() {
+        let moved = neighbors(from).any(|(_, value)| value == to);
+        moved.then_some(()).ok_or(SolveError::IllegalMove(index + 1))?;
+    }
+
+    Ok(())
+}
+
+/// Inverse of `pack`: the blank tile becomes 0 and the rest are shifted back to 1..=8.
+pub fn unpack(field: u32) -> [u32; 9] {
+    let blank = get_blank_pos(field);
+    let mut output = [0; 9];
+    for i in (0..9).filter(|&i| i != blank) {
+        output[i as usize] = get_tile(field, i) + 1;
+    }
+    output
+}
+
+/// Flattens rows of the board into the reading order `pack` and `solve` take.
+pub fn from_2d(grid: [[u32; 3]; 3]) -> [u32; 9] {
+    let mut output = [0; 9];
+    for (cells, row) in output.chunks_mut(3).zip(&grid) {
+        cells.copy_from_slice(row);
+    }
+    output
+}
+
+/// Tiles of the field as rows, the inverse of packing what `from_2d` returns.
+pub fn as_2d(field: u32) -> [[u32; 3]; 3] {
+    let tiles = unpack(field);
+    let mut grid = [[0; 3]; 3];
+    for (row, cells) in grid.iter_mut().zip(tiles.chunks(3)) {
+        row.copy_from_slice(cells);
+    }
+    grid
+}
+
+pub fn solve(input: &[u32; 9]) -> Result<Trace, SolveError> {
+    solve_with(input, &SolveConfig::default())
+}
+
+/// Board states from the input to the goal, one at a time, for consumers that show them as they go.
+/// The whole solution is found up front, so invalid and unsolvable inputs are reported before any state is yielded.
+pub fn solve_iter(input: &[u32; 9]) -> Result<impl Iterator<Item = u32>, SolveError> {
+    solve(input).map(|trace| trace.trace.into_iter())
+}
+
+/// Solves a puzzle written with arbitrary symbols, e.g. letters, `alphabet[0]` being the blank tile
+/// and `alphabet[i]` the tile that ends up i-th in the goal. The trace draws the boards with the same symbols.
+pub fn solve_alphabet<T: PartialEq + core::fmt::Display>(
+    input: &[T; 9],
+    alphabet: &[T; 9],
+) -> Result<Trace, SolveError> {
+    let tiles = translate(input, alphabet)?;
+    solve(&tiles).map(|trace| trace.with_alphabet(alphabet))
+}
+
+pub fn solve_using(input: &[u32; 9], algorithm: Algorithm) -> Result<Trace, SolveError> {
+    solve_with(input, &SolveConfig { algorithm, ..SolveConfig::default() })
+}
+
+/// Solves towards an arbitrary arrangement of the tiles instead of the standard goal.
+pub fn solve_to(input: &[u32; 9], goal: &[u32; 9]) -> Result<Trace, SolveError> {
+    solve_with(input, &SolveConfig { goal: Some(*goal), ..SolveConfig::default() })
+}
+
+/// Solves towards whichever of the goals is the fewest moves away, e.g. when a board and its rotation both count
+/// as solved. The trace ends at the chosen goal, and ties go to the goal listed first.
+///
+/// Fails with `SolveError::NoGoals` if `goals` is empty, and with `SolveError::Unsolvable` if the input
+/// can't reach any of them.
+pub fn solve_to_any(input: &[u32; 9], goals: &[[u32; 9]]) -> Result<Trace, SolveError> {
+    if goals.is_empty() {
+        return Err(SolveError::NoGoals);
+    }
+
+    validate_input(input)?;
+    for goal in goals {
+        validate_input(goal)?;
+    }
+
+    let reachable = goals.iter().filter(|goal| check_solvability(input, goal).is_ok());
+    let outputs = reachable.map(pack).collect::<Vec<_>>();
+    if outputs.is_empty() {
+        return Err(SolveError::Unsolvable { inversions: count_inversions(input) });
+    }
+
+    bfs_search(pack(input), &outputs, usize::MAX, BFS_CAPACITY, false, None).map(|(trace, _)| trace)
+}
+
+/// Goal with the blank tile at `blank_pos` and tiles 1..8 around it in reading order,
+/// e.g. `goal_with_blank(4)` for the blank in the center. `goal_with_blank(8)` is the standard goal.
+///
+/// Shifting the blank doesn't reorder the tiles, so every such goal is reachable from the same inputs.
+///
+/// Panics if `blank_pos` is outside the board.
+pub fn goal_with_blank(blank_pos: usize) -> [u32; 9] {
+    assert!(blank_pos < 9, "blank position {} is outside the board", blank_pos);
+
+    let mut goal = [0; 9];
+    let cells = (0..9).filter(|&i| i != blank_pos);
+    for (cell, tile) in cells.zip(1..) {
+        goal[cell] = tile;
+    }
+    goal
+}
+
+pub fn solve_with(input: &[u32; 9], cfg: &SolveConfig) -> Result<Trace, SolveError> {
+    solve_with_progress(input, cfg, None)
+}
+
+/// Same as `solve_with`, calling `progress` periodically while the search runs.
+/// IDA* doesn't keep a frontier, so it never reports any progress.
+pub fn solve_with_progress(
+    input: &[u32; 9],
+    cfg: &SolveConfig,
+    progress: Option<&mut dyn FnMut(SearchProgress)>,
+) -> Result<Trace, SolveError> {
+    let goal = cfg.goal.unwrap_or_else(goal_array);
+
+    validate_input(input)?;
+    validate_input(&goal)?;
+    check_solvability(input, &goal)?;
+
+    let input = pack(input);
+    let goal = pack(&goal);
+
+    info!("input:  {:#034b}, output: {:#034b}", input, goal);
+
+    let max_nodes = cfg.max_nodes.unwrap_or(usize::MAX);
+    let capacity = cfg.capacity.unwrap_or(BFS_CAPACITY);
+    let canonical = cfg.canonical && transpose_relabeled(goal) == goal;
+    let (trace, stats) = match cfg.algorithm {
+        Algorithm::Bfs => bfs_search(input, &[goal], max_nodes, capacity, canonical, progress)
+            .map(|(trace, stats)| (trace, Some(stats)))?,
+        #[cfg(feature = "std")]
+        Algorithm::AStar => {
+            astar::astar_search(input, goal, ManhattanDistance, 1.0, max_nodes, progress)
+                .map(|(trace, stats)| (trace, Some(stats)))?
+        }
+        Algorithm::IdaStar => {
+            let trace = idastar(input, goal, ManhattanDistance).ok_or_else(|| {
+                SolveError::Unsolvable { inversions: count_inversions(&unpack(input)) }
+            })?;
+            (trace, None)
+        }
+    };
+
+    #[cfg(feature = "std")]
+    if cfg.verbose {
+        eprintln!("input:  {:#034b}\noutput: {:#034b}", input, goal);
+        if let Some(stats) = stats {
+            eprintln!(
+                "expanded {} states, generated {}, at most {} on the frontier",
+                stats.expanded, stats.generated, stats.peak_frontier
+            );
+            if stats.reallocations > 0 {
+                eprintln!("grew the search's containers {} times", stats.reallocations);
+            }
+        }
+        eprintln!("{} moves\n", trace.moves_count());
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = stats;
+
+    Ok(trace)
+}
+
+/// Optimal trace from the input to the output, found by a breadth-first search from the output.
+///
+/// Returns `SolveError::Unsolvable` if the search runs out of states without reaching the input,
+/// and `SolveError::CorruptState(0)` if the output isn't a validly packed field.
+pub fn bfs(input: u32, output: u32) -> Result<Trace, SolveError> {
+    bfs_stats(input, output).map(|(trace, _)| trace)
+}
+
+/// Same as `bfs`, also reporting how much work the search took.
+pub fn bfs_stats(input: u32, output: u32) -> Result<(Trace, SearchStats), SolveError> {
+    bfs_stats_with_capacity(input, output, BFS_CAPACITY)
+}
+
+/// Same as `bfs_stats`, reserving room for `capacity` states instead of `BFS_CAPACITY`, see `SolveConfig::capacity`.
+pub fn bfs_stats_with_capacity(
+    input: u32,
+    output: u32,
+    capacity: usize,
+) -> Result<(Trace, SearchStats), SolveError> {
+    bfs_search(input, &[output], usize::MAX, capacity, false, None)
+}
+
+/// Same as `bfs`, but gives up with `SolveError::LimitExceeded` once more than `max_nodes` states are expanded.
+pub fn bfs_limited(input: u32, output: u32, max_nodes: usize) -> Result<Trace, SolveError> {
+    bfs_search(input, &[output], max_nodes, BFS_CAPACITY, false, None).map(|(trace, _)| trace)
+}
+
+/// First move of an optimal solution from `field` to the standard goal, e.g. for a hint button in a game.
+///
+/// Returns None if the field is already solved or can't reach the goal at all.
+/// Every call runs a BFS, so to answer many hints keep a `distance_table` and move to a neighbor one closer instead.
+pub fn hint(field: u32) -> Option<Direction> {
+    bfs(field, GOAL).ok()?.directions().first().copied()
+}
+
+// breadth-first search from all of the outputs at once, ending at whichever is the closest to the input
+//
+// With `canonical` set it visits a single state of every pair the symmetry maps together, which has to map
+// every output onto itself. Parents are then recorded for the canonical state, while the search goes on
+// from whichever of the two it came across, so a parent may belong to the other state of the pair.
+fn bfs_search(
+    input: u32,
+    outputs: &[u32],
+    max_nodes: usize,
+    capacity: usize,
+    canonical: bool,
+    mut progress: Option<&mut dyn FnMut(SearchProgress)>,
+) -> Result<(Trace, SearchStats), SolveError> {
+    // every state the search visits is a neighbor of an output, and only valid fields have a rank
+    if let Some(index) = outputs.iter().position(|&output| !is_packed(output)) {
+        return Err(SolveError::CorruptState(index));
+    }
+
+    let key = |field| if canonical { symmetry::canonical(field) } else { field };
+
+    let mut tree = Tree::new(capacity);
+    let mut moves = VecDeque::with_capacity(capacity);
+    let mut stats = SearchStats::default();
+
+    for &output in outputs {
+        if tree.insert(key(output), output) {
+            moves.push_back(output);
+        }
+    }
+    stats.peak_frontier = moves.len();
+
+    // an input that is already an output needs no search at all and ends up as the only state of the trace
+    let mut found = outputs.contains(&input);
+    let mut reached = input;
+
+    // states of the current depth still in the queue, all the following ones being a move further
+    let mut depth = 0;
+    let mut left_in_level = moves.len();
+
+    while !found {
+        if stats.expanded == max_nodes {
+            return Err(SolveError::LimitExceeded);
+        }
+
+        let current = match moves.pop_front() {
+            Some(field) => field,
+            None => {
+                return Err(SolveError::Unsolvable { inversions: count_inversions(&unpack(input)) })
+            }
+        };
+        stats.expanded += 1;
+
+        let reserved = (moves.capacity(), tree.capacity());
+        for (_, value) in neighbors(current) {
+            if tree.insert(key(value), current) {
+                moves.push_back(value);
+                stats.generated += 1;
+            }
+        }
+        if moves.capacity() != reserved.0 || tree.capacity() != reserved.1 {
+            stats.reallocations += 1;
+        }
+
+        stats.peak_frontier = stats.peak_frontier.max(moves.len());
+
+        if stats.expanded.is_multiple_of(PROGRESS_INTERVAL) {
+            if let Some(progress) = progress.as_mut() {
+                progress(SearchProgress { depth, expanded: stats.expanded, frontier: moves.len() });
+            }
+        }
+
+        left_in_level -= 1;
+        if left_in_level == 0 {
+            depth += 1;
+            left_in_level = moves.len();
+            debug!("depth {}: {} states on the frontier", depth, moves.len());
+        }
+
+        found = key(current) == key(input);
+        reached = current;
+    }
+
+    // a parent recorded for the other state of the pair is a move away from its mirror image instead
+    let parent = |field| {
+        let parent = tree.parent(key(field));
+        let adjacent = parent == field || neighbors(field).any(|(_, value)| value == parent);
+        if adjacent {
+            parent
+        } else {
+            transpose_relabeled(parent)
+        }
+    };
+
+    let mut current = reached;
+    let mut trace = vec![current];
+
+    while current != parent(current) {
+        current = parent(current);
+        trace.push(current);
+    }
+
+    // the search may have come across the mirror image of the input, whose path mirrors back onto the goal
+    if reached != input {
+        trace.iter_mut().for_each(|field| *field = transpose_relabeled(*field));
+    }
+
+    debug!("found a path of {} moves after expanding {} states", trace.len() - 1, stats.expanded);
+
+    Ok((Trace::new(trace), stats))
+}
+
+/// Index of the field among all 9! arrangements of the board, in lexicographic order of their tiles.
+///
+/// This is the Lehmer code of the permutation: every tile contributes the number of smaller tiles
+/// still left after it, weighted by the number of arrangements of the remaining cells.
+/// Same as `factoriadic::rank(&unpack(field))`, only without unpacking the field.
+pub fn rank(field: u32) -> usize {
+    const WEIGHTS: [usize; 9] =
+        [fact(8), fact(7), fact(6), fact(5), fact(4), fact(3), fact(2), fact(1), fact(0)];
+
+    // number of set bits in every 9-bit set of tiles, count_ones is slow without a popcnt instruction
+    const ONES: [u8; 512] = {
+        let mut ones = [0; 512];
+        let mut i = 1;
+        while i < 512 {
+            ones[i] = ones[i / 2] + (i % 2) as u8;
+            i += 1;
+        }
+        ones
+    };
+
+    let blank = get_blank_pos(field) as usize;
+    let mut tiles = field;
+    let mut seen = 0;
+    let mut rank = 0;
+
+    for (i, weight) in WEIGHTS.iter().enumerate() {
+        let tile = if i == blank { 0 } else { (tiles & 0b111) + 1 };
+        tiles >>= 3;
+
+        let smaller_left = tile - ONES[seen & ((1 << tile) - 1)] as u32;
+        seen |= 1 << tile;
+        rank += smaller_left as usize * weight;
+    }
+
+    rank
+}
+
+/// The field with the given `rank`.
+pub fn unrank(index: usize) -> u32 {
+    let tiles = factoriadic::unrank(index as u128, 9);
+    pack(&tiles.try_into().unwrap())
+}
+
+// Parents of the states visited by BFS. Every state has a unique rank, so by default
+// they live in a flat array instead of a hash map, which is both faster and smaller.
+#[cfg(not(feature = "hashmap"))]
+struct Tree(Vec<u32>);
+
+#[cfg(not(feature = "hashmap"))]
+impl Tree {
+    // bit 31 is never set in a packed field
+    const UNVISITED: u32 = u32::MAX;
+
+    // the table always covers every rank, so there is nothing to reserve
+    fn new(_capacity: usize) -> Self {
+        Tree(vec![Self::UNVISITED; fact(9)])
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.len()
+    }
+
+    // returns whether the state wasn't visited before
+    fn insert(&mut self, field: u32, parent: u32) -> bool {
+        let slot = &mut self.0[rank(field)];
+        (*slot == Self::UNVISITED).then(|| *slot = parent).is_some()
+    }
+
+    fn parent(&self, field: u32) -> u32 {
+        self.0[rank(field)]
+    }
+}
+
+#[cfg(feature = "hashmap")]
+struct Tree(HashMap<u32, u32>);
+
+#[cfg(feature = "hashmap")]
+impl Tree {
+    fn new(capacity: usize) -> Self {
+        Tree(HashMap::with_capacity_and_hasher(capacity, Default::default()))
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    fn insert(&mut self, field: u32, parent: u32) -> bool {
+        let mut inserted = false;
+        self.0.entry(field).or_insert_with(|| {
+            inserted = true;
+            parent
+        });
+        inserted
+    }
+
+    fn parent(&self, field: u32) -> u32 {
+        self.0[&field]
+    }
+}
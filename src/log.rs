@@ -0,0 +1,60 @@
+// Leveled diagnostics for following what the searches do, written to stderr with the `tracing` feature.
+//
+// The level comes from the SUPERZUB_LOG environment variable: `info` by default, `debug` for more
+// detail or `off`. Without the feature the macros compile to nothing, while still type checking
+// their arguments so that values computed only for logging don't trigger unused warnings.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Level {
+    Info,
+    Debug,
+}
+
+impl Level {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn enabled(level: Level) -> bool {
+    static MAX_LEVEL: std::sync::OnceLock<Option<Level>> = std::sync::OnceLock::new();
+
+    let max_level = MAX_LEVEL.get_or_init(|| match std::env::var("SUPERZUB_LOG").as_deref() {
+        Ok("off") => None,
+        Ok("debug") => Some(Level::Debug),
+        _ => Some(Level::Info),
+    });
+
+    max_level.is_some_and(|max_level| level <= max_level)
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            std::eprintln!("[{} superzub] {}", $level.name(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if false {
+            let _ = ($level, format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { log!($crate::log::Level::Info, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { log!($crate::log::Level::Debug, $($arg)*) };
+}
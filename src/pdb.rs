@@ -0,0 +1,227 @@
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::board::Board;
+
+// Disjoint additive pattern database for `Board`.
+//
+// The tiles are split into groups, and for every group it stores the number of moves of the group's own tiles
+// needed to bring them home from each of their placements, no matter where the other tiles are.
+// A move shifts a single tile, which belongs to at most one group, so the costs of different groups
+// can be added up without overestimating the number of moves left.
+//
+// The costs are found by a BFS from the goal over the group's placements and the blank position,
+// where moving one of the group's tiles costs a move and moving any other tile is free.
+//
+// File layout, all numbers single bytes: the magic `SZPDB`, rows, columns, number of groups,
+// then for every group its number of tiles, the tiles as `Board::pack` takes them, and the cost
+// of every placement of those tiles in the order `rank` enumerates them.
+
+const MAGIC: &[u8] = b"SZPDB";
+
+/// The usual partition of the 15-puzzle into groups of 6, 6 and 3 tiles.
+pub const FIFTEEN_PUZZLE_663: [&[u32]; 3] =
+    [&[1, 5, 6, 9, 10, 13], &[7, 8, 11, 12, 14, 15], &[2, 3, 4]];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternDatabase<const R: usize, const C: usize> {
+    groups: Vec<Group>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Group {
+    // tile indices as `Board::get_tile` returns them
+    tiles: Vec<u32>,
+    // moves left for every placement of the tiles, indexed by `rank`
+    costs: Vec<u8>,
+}
+
+impl<const R: usize, const C: usize> PatternDatabase<R, C> {
+    const CELLS: usize = R * C;
+
+    /// Computes the costs for every group of tiles, numbered as `Board::pack` takes them.
+    /// Takes time and memory proportional to the number of placements of the largest group times the number of cells,
+    /// which is around 92 million for 6 tiles of the 15-puzzle, so it's meant to be saved and loaded afterwards.
+    ///
+    /// Panics if the groups overlap or name a tile that isn't on the board.
+    pub fn generate(groups: &[&[u32]]) -> Self {
+        let mut seen = vec![false; Self::CELLS];
+        for &tile in groups.iter().copied().flatten() {
+            assert!((1..Self::CELLS as u32).contains(&tile), "tile {} isn't on the board", tile);
+            assert!(!seen[tile as usize], "tile {} belongs to more than one group", tile);
+            seen[tile as usize] = true;
+        }
+
+        let groups = groups
+            .iter()
+            .map(|tiles| {
+                let tiles = tiles.iter().map(|&tile| tile - 1).collect::<Vec<_>>();
+                let costs = Self::costs(&tiles);
+                Group { tiles, costs }
+            })
+            .collect();
+
+        PatternDatabase { groups }
+    }
+
+    fn costs(tiles: &[u32]) -> Vec<u8> {
+        let cells = Self::CELLS;
+        let mut costs = vec![u8::MAX; placements(tiles.len(), cells)];
+        let mut visited = vec![0u64; (costs.len() * cells).div_ceil(64)];
+        let mut queue = VecDeque::new();
+
+        // tile index i sits in cell i in the goal
+        let goal = tiles.iter().map(|&tile| tile as usize).collect::<Vec<_>>();
+        queue.push_back((rank(&goal, cells), cells - 1, 0u8));
+
+        while let Some((index, blank, cost)) = queue.pop_front() {
+            let key = index * cells + blank;
+            if visited[key / 64] & (1 << (key % 64)) != 0 {
+                continue;
+            }
+            visited[key / 64] |= 1 << (key % 64);
+
+            // free moves are queued in front, so the first time a placement comes up is the cheapest
+            costs[index] = costs[index].min(cost);
+
+            let positions = unrank(index, tiles.len(), cells);
+            for next in Self::neighbors(blank) {
+                match positions.iter().position(|&pos| pos == next) {
+                    Some(moved) => {
+                        let mut positions = positions.clone();
+                        positions[moved] = blank;
+                        queue.push_back((rank(&positions, cells), next, cost + 1));
+                    }
+                    None => queue.push_front((index, next, cost)),
+                }
+            }
+        }
+
+        costs
+    }
+
+    fn neighbors(blank: usize) -> impl Iterator<Item = usize> {
+        let up = (blank >= C).then(|| blank - C);
+        let down = (blank < Self::CELLS - C).then(|| blank + C);
+        let left = (!blank.is_multiple_of(C)).then(|| blank - 1);
+        let right = (blank % C != C - 1).then(|| blank + 1);
+        IntoIterator::into_iter([up, down, left, right]).flatten()
+    }
+
+    /// Sum of the costs of every group's placement on the board. Tiles outside of the groups aren't counted,
+    /// so it's an admissible estimate for `Board::solve_with` whichever tiles the groups cover.
+    pub fn estimate(&self, board: Board<R, C>) -> u32 {
+        let blank = board.get_blank_pos();
+        let mut cell_of = [0; 32];
+        for i in (0..Self::CELLS).filter(|&i| i != blank) {
+            cell_of[board.get_tile(i) as usize] = i;
+        }
+
+        let mut positions = [0; 32];
+        self.groups
+            .iter()
+            .map(|group| {
+                for (pos, &tile) in positions.iter_mut().zip(&group.tiles) {
+                    *pos = cell_of[tile as usize];
+                }
+                group.costs[rank(&positions[..group.tiles.len()], Self::CELLS)] as u32
+            })
+            .sum()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+
+        out.write_all(MAGIC)?;
+        out.write_all(&[R as u8, C as u8, self.groups.len() as u8])?;
+        for group in &self.groups {
+            out.write_all(&[group.tiles.len() as u8])?;
+            out.write_all(&group.tiles.iter().map(|&tile| tile as u8 + 1).collect::<Vec<_>>())?;
+            out.write_all(&group.costs)?;
+        }
+
+        out.flush()
+    }
+
+    /// Reads a database written by `save` for a board of the same shape.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        std::fs::File::open(path)?.read_to_end(&mut bytes)?;
+
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut rest =
+            bytes.strip_prefix(MAGIC).ok_or_else(|| invalid("not a pattern database"))?;
+        let mut take = |len: usize| {
+            (len <= rest.len())
+                .then(|| {
+                    let (taken, left) = rest.split_at(len);
+                    rest = left;
+                    taken
+                })
+                .ok_or_else(|| invalid("unexpected end of file"))
+        };
+
+        if take(2)? != [R as u8, C as u8] {
+            return Err(invalid("database is for a board of another shape"));
+        }
+
+        // a tile counted in two groups would have its moves added twice, overestimating the cost
+        let mut seen = vec![false; Self::CELLS];
+        let mut groups = Vec::new();
+        for _ in 0..take(1)?[0] {
+            let len = take(1)?[0] as usize;
+            let tiles = take(len.min(Self::CELLS))?;
+            if len >= Self::CELLS
+                || tiles.iter().any(|&tile| tile == 0 || tile as usize >= Self::CELLS)
+            {
+                return Err(invalid("tile isn't on the board"));
+            }
+            for &tile in tiles {
+                if seen[tile as usize] {
+                    return Err(invalid("tile belongs to more than one group"));
+                }
+                seen[tile as usize] = true;
+            }
+
+            let tiles = tiles.iter().map(|&tile| tile as u32 - 1).collect::<Vec<_>>();
+            let costs = take(placements(len, Self::CELLS))?.to_vec();
+            groups.push(Group { tiles, costs });
+        }
+
+        if !rest.is_empty() {
+            return Err(invalid("trailing bytes after the last group"));
+        }
+
+        Ok(PatternDatabase { groups })
+    }
+}
+
+// number of ways to put `tiles` distinct tiles into `cells` cells
+fn placements(tiles: usize, cells: usize) -> usize {
+    (cells - tiles + 1..=cells).product()
+}
+
+// index of the placement among all placements of as many tiles, every position being counted
+// among the cells the previous tiles left free
+fn rank(positions: &[usize], cells: usize) -> usize {
+    positions.iter().enumerate().fold(0, |index, (i, &pos)| {
+        let taken = positions[..i].iter().filter(|&&prev| prev < pos).count();
+        index * (cells - i) + pos - taken
+    })
+}
+
+fn unrank(mut index: usize, tiles: usize, cells: usize) -> Vec<usize> {
+    let mut digits = vec![0; tiles];
+    for i in (0..tiles).rev() {
+        digits[i] = index % (cells - i);
+        index /= cells - i;
+    }
+
+    let mut positions = Vec::with_capacity(tiles);
+    for digit in digits {
+        let pos = (0..cells).filter(|pos| !positions.contains(pos)).nth(digit).unwrap();
+        positions.push(pos);
+    }
+    positions
+}
@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+
+use crate::{check_solvability, goal_array, neighbors, GOAL};
+
+/// SplitMix64, which is plenty for shuffling boards and keeps runs reproducible for a given seed.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, bound).
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        // reject the incomplete last stride of u64 to avoid modulo bias
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % bound;
+            }
+        }
+    }
+}
+
+/// Uniformly random solvable puzzle, the same for the same seed.
+///
+/// Swapping two tiles flips the parity of inversions, so swapping the same two tiles
+/// of every unsolvable permutation maps them one-to-one onto the solvable ones.
+pub fn random_solvable(seed: u64) -> [u32; 9] {
+    let mut rng = Rng::new(seed);
+
+    let mut tiles = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+    for i in (1..tiles.len()).rev() {
+        tiles.swap(i, rng.below(i as u64 + 1) as usize);
+    }
+
+    if check_solvability(&tiles, &goal_array()).is_err() {
+        let (a, b) = match tiles.iter().position(|&x| x == 0) {
+            Some(0) | Some(1) => (7, 8),
+            _ => (0, 1),
+        };
+        tiles.swap(a, b);
+    }
+
+    tiles
+}
+
+/// Applies `moves` random moves to the goal, never undoing the previous move right away.
+/// The result is solvable by construction and at most `moves` moves away from the goal.
+pub fn scramble(moves: usize, seed: u64) -> u32 {
+    let mut rng = Rng::new(seed);
+
+    let mut previous = None;
+    let mut field = GOAL;
+
+    for _ in 0..moves {
+        let legal = neighbors(field)
+            .filter(|&(direction, _)| previous != Some(direction.opposite()))
+            .collect::<Vec<_>>();
+
+        let (direction, next) = legal[rng.below(legal.len() as u64) as usize];
+        field = next;
+        previous = Some(direction);
+    }
+
+    field
+}
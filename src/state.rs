@@ -0,0 +1,139 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{down, get_blank_pos, get_tile, left, pack, right, unpack, up};
+
+/// Packed 3 by 3 field, see the layout at the top of lib.rs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct State(pub u32);
+
+impl State {
+    /// Tile values in reading order, 0 being the blank tile.
+    pub fn tiles(self) -> [u32; 9] {
+        unpack(self.0)
+    }
+
+    /// Tile value in cell i in reading order, 0 being the blank tile. Same as `state[i]`.
+    pub fn get(self, i: usize) -> u32 {
+        assert!(i < 9, "cell {} is outside the board", i);
+
+        if i as u32 == get_blank_pos(self.0) {
+            0
+        } else {
+            get_tile(self.0, i as u32) + 1
+        }
+    }
+
+    /// The state after moving the blank tile up, or None if it is on the top row.
+    pub fn up(self) -> Option<State> {
+        self.step(up)
+    }
+
+    /// The state after moving the blank tile down, or None if it is on the bottom row.
+    pub fn down(self) -> Option<State> {
+        self.step(down)
+    }
+
+    /// The state after moving the blank tile left, or None if it is in the left column.
+    pub fn left(self) -> Option<State> {
+        self.step(left)
+    }
+
+    /// The state after moving the blank tile right, or None if it is in the right column.
+    pub fn right(self) -> Option<State> {
+        self.step(right)
+    }
+
+    // the field functions leave the field unchanged when the blank tile runs into a wall
+    fn step(self, make_move: fn(u32) -> u32) -> Option<State> {
+        let moved = make_move(self.0);
+        (moved != self.0).then_some(State(moved))
+    }
+}
+
+// every value a tile can have, for `Index` to hand out references to
+static TILE_VALUES: [u32; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+
+impl core::ops::Index<usize> for State {
+    type Output = u32;
+
+    /// Tile value in cell i, like `get`. The values are packed, so there is no tile to borrow,
+    /// and the reference points into a static table of all values instead.
+    ///
+    /// Panics if i is 9 or more.
+    fn index(&self, i: usize) -> &u32 {
+        &TILE_VALUES[self.get(i) as usize]
+    }
+}
+
+impl From<u32> for State {
+    fn from(field: u32) -> Self {
+        State(field)
+    }
+}
+
+impl From<State> for u32 {
+    fn from(state: State) -> Self {
+        state.0
+    }
+}
+
+impl From<&[u32; 9]> for State {
+    fn from(input: &[u32; 9]) -> Self {
+        State(pack(input))
+    }
+}
+
+impl core::fmt::Display for State {
+    /// Draws the board framed like the one at the top of lib.rs, with tiles numbered from 1.
+    ///
+    /// The alternate form `{:#}` prints bare rows of 0-based tile indices instead.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            let blank = get_blank_pos(self.0);
+            for i in 0..9 {
+                if i != blank {
+                    write!(f, "{} ", get_tile(self.0, i))?;
+                } else {
+                    write!(f, "  ")?;
+                }
+                if i % 3 == 2 {
+                    writeln!(f)?;
+                }
+            }
+            return Ok(());
+        }
+
+        Framed { field: self.0, alphabet: None }.fmt(f)
+    }
+}
+
+/// Board drawn in a frame, with either the tile values or their symbols from an alphabet.
+pub(crate) struct Framed<'a> {
+    pub(crate) field: u32,
+    pub(crate) alphabet: Option<&'a [String]>,
+}
+
+impl core::fmt::Display for Framed<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let symbols = match self.alphabet {
+            Some(alphabet) => alphabet.to_vec(),
+            None => (0..9)
+                .map(|tile| if tile == 0 { String::new() } else { tile.to_string() })
+                .collect(),
+        };
+
+        // cells are as wide as the widest symbol, so the columns stay aligned
+        let width = symbols.iter().map(|symbol| symbol.chars().count()).max().unwrap_or(1);
+        let border = format!("+{}", format!("{}+", "-".repeat(width + 2)).repeat(3));
+
+        writeln!(f, "{}", border)?;
+        for row in unpack(self.field).chunks(3) {
+            for &tile in row {
+                write!(f, "| {:^width$} ", symbols[tile as usize], width = width)?;
+            }
+            writeln!(f, "|")?;
+        }
+        f.write_str(&border)
+    }
+}
@@ -0,0 +1,56 @@
+// Boards drawn as standalone SVG images, one per state, so that a trace can be turned into
+// animation frames by other tools. Every cell is a square of CELL units with the tile's number
+// in the middle, the blank cell is left empty.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{get_blank_pos, get_tile, Trace};
+
+const CELL: u32 = 100;
+const GAP: u32 = 4;
+
+/// SVG image of the board, 300 by 300 units, with tiles numbered from 1.
+pub fn to_svg(state: u32) -> String {
+    let size = CELL * 3;
+    let mut svg = String::new();
+
+    // writing into a String never fails
+    let _ = write!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"##,
+        size
+    );
+    let _ = write!(svg, r##"<rect width="{0}" height="{0}" fill="#444"/>"##, size);
+
+    let blank = get_blank_pos(state);
+    for i in (0..9).filter(|&i| i != blank) {
+        let (x, y) = ((i % 3) * CELL, (i / 3) * CELL);
+
+        let _ = write!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{2}" height="{2}" rx="8" fill="#eed9a4" stroke="#444"/>"##,
+            x + GAP / 2,
+            y + GAP / 2,
+            CELL - GAP
+        );
+        let _ = write!(
+            svg,
+            r##"<text x="{}" y="{}" font-family="sans-serif" font-size="48" text-anchor="middle" dominant-baseline="central">{}</text>"##,
+            x + CELL / 2,
+            y + CELL / 2,
+            get_tile(state, i) + 1
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+impl Trace {
+    /// One `to_svg` image for every state, from the input to the goal.
+    pub fn to_svg_frames(&self) -> Vec<String> {
+        self.states().map(to_svg).collect()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{get_blank_pos, get_tile, to_pos};
+
+// Symmetries of the square board, moving the tiles between cells without changing their values.
+// Every transform is a table of the cell each cell of the result takes its tile from.
+//
+// Tiles keep their values, so a transformed state can end up in the other half of the state space:
+// mirroring the standard goal left to right makes it unsolvable.
+//
+// None of them maps the standard goal onto itself. The transposition does once every tile is also renamed
+// after the cell its home goes to, as the blank's home in the bottom right corner lies on the diagonal.
+// The other symmetries move that corner, so the transposition and the identity are the only ones
+// that preserve the goal, and with it the number of moves to the goal.
+
+const ROTATE90: [u32; 9] = [6, 3, 0, 7, 4, 1, 8, 5, 2];
+const ROTATE180: [u32; 9] = [8, 7, 6, 5, 4, 3, 2, 1, 0];
+const ROTATE270: [u32; 9] = [2, 5, 8, 1, 4, 7, 0, 3, 6];
+const REFLECT_HORIZONTAL: [u32; 9] = [2, 1, 0, 5, 4, 3, 8, 7, 6];
+const REFLECT_VERTICAL: [u32; 9] = [6, 7, 8, 3, 4, 5, 0, 1, 2];
+const TRANSPOSE: [u32; 9] = [0, 3, 6, 1, 4, 7, 2, 5, 8];
+const ANTI_TRANSPOSE: [u32; 9] = [8, 5, 2, 7, 4, 1, 6, 3, 0];
+
+fn transform(field: u32, source: &[u32; 9]) -> u32 {
+    let blank = get_blank_pos(field);
+
+    (0..9).zip(source).fold(0, |result, (i, &from)| {
+        if from == blank {
+            result | to_pos(i)
+        } else {
+            result | get_tile(field, from) << (i * 3)
+        }
+    })
+}
+
+/// Rotates the board clockwise by 90 degrees.
+pub fn rotate90(field: u32) -> u32 {
+    transform(field, &ROTATE90)
+}
+
+pub fn rotate180(field: u32) -> u32 {
+    transform(field, &ROTATE180)
+}
+
+/// Rotates the board counterclockwise by 90 degrees.
+pub fn rotate270(field: u32) -> u32 {
+    transform(field, &ROTATE270)
+}
+
+/// Mirrors the board left to right, swapping its first and last columns.
+pub fn reflect_horizontal(field: u32) -> u32 {
+    transform(field, &REFLECT_HORIZONTAL)
+}
+
+/// Mirrors the board top to bottom, swapping its first and last rows.
+pub fn reflect_vertical(field: u32) -> u32 {
+    transform(field, &REFLECT_VERTICAL)
+}
+
+/// Mirrors the board along the diagonal from the top left corner, turning rows into columns.
+pub fn transpose(field: u32) -> u32 {
+    transform(field, &TRANSPOSE)
+}
+
+/// Mirrors the board along the diagonal from the top right corner.
+pub fn anti_transpose(field: u32) -> u32 {
+    transform(field, &ANTI_TRANSPOSE)
+}
+
+/// All 8 symmetries of the board applied to the field, starting with the field itself.
+pub fn symmetries(field: u32) -> [u32; 8] {
+    [
+        field,
+        rotate90(field),
+        rotate180(field),
+        rotate270(field),
+        reflect_horizontal(field),
+        reflect_vertical(field),
+        transpose(field),
+        anti_transpose(field),
+    ]
+}
+
+/// Transposes the board and renames every tile after the cell its home cell goes to, so that the standard goal
+/// stays the same and every state keeps its number of moves to the goal.
+pub fn transpose_relabeled(field: u32) -> u32 {
+    let transposed = transpose(field);
+    let blank = get_blank_pos(transposed);
+
+    (0..9).filter(|&i| i != blank).fold(to_pos(blank), |result, i| {
+        let home = get_tile(transposed, i);
+        result | TRANSPOSE[home as usize] << (i * 3)
+    })
+}
+
+/// Smallest of the field and its relabeled transposition, the same for both, which are equally far
+/// from the standard goal. Distances can be computed for canonical states only, roughly halving the states to keep.
+pub fn canonical(field: u32) -> u32 {
+    field.min(transpose_relabeled(field))
+}
@@ -0,0 +1,26 @@
+// Entry point for running the solver in a browser, taking and returning plain strings, the types
+// a `#[wasm_bindgen]` export can pass to JavaScript without glue of its own.
+//
+// The attribute isn't added here, as wasm-bindgen isn't a dependency, and a plain `pub fn` isn't exported
+// from a wasm32-unknown-unknown build. The embedder wraps `solve_json` in a function of their own that
+// carries the attribute.
+//
+// Everything the binary prints lives in main.rs and isn't on this path: the puzzle comes in as an argument
+// and the solution goes back as the return value. The searches' diagnostics go through the macros in
+// log.rs, which compile to nothing without the `tracing` feature, and the work summary is only printed
+// with `SolveConfig::verbose`, which `solve` leaves off. Either way stderr goes nowhere on
+// wasm32-unknown-unknown.
+
+use crate::json::Value;
+use crate::{parse, solve};
+
+/// Solves a puzzle given as 9 whitespace separated tiles, like `parse` expects, returning
+/// `Trace::solution_json` or `{"error":"..."}`.
+pub fn solve_json(input: &str) -> String {
+    match parse(input).and_then(|input| solve(&input)) {
+        Ok(trace) => trace.solution_json(),
+        Err(err) => {
+            Value::Object(vec![("error".to_owned(), Value::String(err.to_string()))]).to_string()
+        }
+    }
+}
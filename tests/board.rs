@@ -0,0 +1,117 @@
+use superzub::{solvable_count, Board, SolveError};
+
+#[rustfmt::skip]
+const SOLVABLE: [[u32; 16]; 3] = [
+    [ 1,  2,  3,  4,
+      5,  6,  7,  8,
+      9, 10, 11, 12,
+     13, 14, 15,  0],
+    [12,  1, 10,  2,
+      7, 11,  4, 14,
+      5,  0,  9, 15,
+      8, 13,  6,  3],
+    [ 1,  2,  3,  4,
+      5,  6,  7,  8,
+      9, 10,  0, 11,
+     13, 14, 15, 12],
+];
+
+#[rustfmt::skip]
+const UNSOLVABLE: [[u32; 16]; 3] = [
+    // Sam Loyd's 14-15 puzzle
+    [ 1,  2,  3,  4,
+      5,  6,  7,  8,
+      9, 10, 11, 12,
+     13, 15, 14,  0],
+    [ 3,  9,  1, 15,
+     14, 11,  4,  6,
+     13,  0, 10, 12,
+      2,  7,  8,  5],
+    // no inversions, which is all the odd width rule looks at, but the blank is a row too high
+    [ 1,  2,  3,  4,
+      5,  6,  7,  8,
+      9, 10, 11,  0,
+     12, 13, 14, 15],
+];
+
+#[test]
+fn solvable_15_puzzles() {
+    for input in &SOLVABLE {
+        assert!(Board::<4, 4>::pack(input).unwrap().is_solvable(), "{:?}", input);
+    }
+}
+
+#[test]
+fn unsolvable_15_puzzles() {
+    for input in &UNSOLVABLE {
+        assert!(!Board::<4, 4>::pack(input).unwrap().is_solvable(), "{:?}", input);
+        assert!(matches!(Board::<4, 4>::solve(input), Err(SolveError::Unsolvable { .. })));
+    }
+}
+
+#[test]
+fn solves_easy_15_puzzle() {
+    let trace = Board::<4, 4>::solve(&SOLVABLE[2]).unwrap();
+
+    assert_eq!(trace.len(), 3);
+    assert_eq!(trace[2], Board::goal());
+}
+
+#[test]
+fn solvability_of_2x2_boards() {
+    assert!(Board::<2, 2>::pack(&[0, 1, 3, 2]).unwrap().is_solvable());
+    assert!(!Board::<2, 2>::pack(&[0, 1, 2, 3]).unwrap().is_solvable());
+    assert!(Board::<2, 2>::solve(&[3, 1, 0, 2]).is_ok());
+}
+
+#[test]
+fn packs_rows() {
+    let grid = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 0]];
+    assert_eq!(Board::from_2d(grid).unwrap(), Board::<4, 4>::goal());
+}
+
+#[test]
+fn solves_2x3_puzzle() {
+    let input = Board::<2, 3>::from_2d([[4, 1, 3], [0, 2, 5]]).unwrap();
+    assert!(input.is_solvable());
+
+    let trace = Board::<2, 3>::solve(&[4, 1, 3, 0, 2, 5]).unwrap();
+    assert_eq!(trace[0], input);
+    assert_eq!(trace.last(), Some(&Board::goal()));
+    assert_eq!(format!("{}", Board::<2, 3>::goal()), " 0  1  2 \n 3  4    \n");
+}
+
+#[test]
+fn solvability_of_rectangular_boards() {
+    // 2 columns is even, so the blank's row counts
+    assert!(Board::<3, 2>::pack(&[1, 2, 3, 4, 5, 0]).unwrap().is_solvable());
+    assert!(!Board::<3, 2>::pack(&[1, 2, 3, 0, 4, 5]).unwrap().is_solvable());
+    assert!(Board::<3, 2>::solve(&[1, 2, 3, 0, 5, 4]).is_ok());
+    assert!(!Board::<2, 3>::pack(&[2, 1, 3, 4, 5, 0]).unwrap().is_solvable());
+}
+
+#[test]
+fn counts_solvable_states() {
+    assert_eq!(solvable_count(2), 12);
+    assert_eq!(solvable_count(3), 181440);
+    assert_eq!(solvable_count(4), 10461394944000);
+}
+
+#[test]
+fn rejects_invalid_tiles() {
+    assert!(matches!(Board::<2, 2>::pack(&[0, 1, 2]), Err(SolveError::WrongTileCount(3))));
+    assert!(matches!(Board::<2, 2>::pack(&[0, 1, 2, 4]), Err(SolveError::OutOfRange(4))));
+    assert!(matches!(Board::<2, 2>::pack(&[0, 1, 2, 0]), Err(SolveError::DuplicateBlank)));
+    assert!(matches!(Board::<2, 2>::pack(&[3, 1, 2, 2]), Err(SolveError::MissingBlank)));
+    assert!(matches!(Board::<2, 2>::pack(&[0, 1, 1, 3]), Err(SolveError::DuplicateTile(1))));
+}
+
+#[test]
+fn finds_coordinates_on_rectangular_boards() {
+    let board = Board::<2, 3>::from_2d([[4, 1, 3], [0, 2, 5]]).unwrap();
+    assert_eq!(board.blank_coords(), (1, 0));
+    assert_eq!(board.tile_coords(0), (1, 0));
+    assert_eq!(board.tile_coords(4), (0, 0));
+    assert_eq!(board.tile_coords(5), (1, 2));
+    assert_eq!(Board::<4, 4>::goal().tile_coords(15), (3, 2));
+}
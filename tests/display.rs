@@ -0,0 +1,113 @@
+use superzub::{
+    display_annotated, pack, solve, solve_alphabet, to_svg, ManhattanDistance, Puzzle, SolveError,
+    State, GOAL,
+};
+
+#[test]
+fn draws_framed_board() {
+    let state = State::from(&[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    let expected = "\
++---+---+---+
+| 1 | 2 | 3 |
+| 4 | 5 |   |
+| 6 | 7 | 8 |
++---+---+---+";
+    assert_eq!(state.to_string(), expected);
+    assert_eq!(format!("{:#}", state), "0 1 2 \n3 4   \n5 6 7 \n");
+}
+
+#[test]
+fn stacks_trace_boards() {
+    let trace = solve(&[1, 2, 3, 4, 5, 6, 7, 0, 8]).unwrap();
+
+    let framed = trace.to_string();
+    assert_eq!(framed.split("\n\n").count(), 2);
+    assert!(framed.ends_with("+---+---+---+"));
+
+    assert_eq!(format!("{:#}", trace), "0 1 2 \n3 4 5 \n6   7 \n\n0 1 2 \n3 4 5 \n6 7   \n\n");
+}
+
+#[test]
+fn draws_trace_with_alphabet() {
+    let alphabet = [' ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+    let trace = solve_alphabet(&['a', 'b', 'c', 'd', 'e', 'f', 'g', ' ', 'h'], &alphabet).unwrap();
+
+    let expected = "\
++---+---+---+
+| a | b | c |
+| d | e | f |
+| g |   | h |
++---+---+---+
+
++---+---+---+
+| a | b | c |
+| d | e | f |
+| g | h |   |
++---+---+---+";
+    assert_eq!(trace.to_string(), expected);
+
+    let emoji = [" ", "🍎", "🍐", "🍊", "🍋", "🍌", "🍉", "🍇", "🍓"];
+    let input = ["🍎", "🍐", "🍊", "🍋", "🍌", "🍉", "🍇", "🍓", " "];
+    assert_eq!(
+        solve_alphabet(&input, &emoji).unwrap().to_string().lines().nth(1),
+        Some("| 🍎 | 🍐 | 🍊 |")
+    );
+}
+
+#[test]
+fn rejects_symbols_outside_alphabet() {
+    let alphabet = ['_', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+    let input = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'x', 'h'];
+    assert!(matches!(solve_alphabet(&input, &alphabet), Err(SolveError::AlphabetMismatch { .. })));
+}
+
+#[test]
+fn draws_svg_frames() {
+    let state = State::from(&[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    let svg = to_svg(state.0);
+
+    assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>"));
+    // a background and 8 tiles, numbered 1 to 8
+    assert_eq!(svg.matches("<rect").count(), 9);
+    for tile in 1..=8 {
+        assert!(svg.contains(&format!(">{}</text>", tile)));
+    }
+    assert!(!svg.contains(">0</text>"));
+
+    let frames = solve(&[1, 2, 3, 4, 5, 6, 7, 0, 8]).unwrap().to_svg_frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1], to_svg(State::from(&[1, 2, 3, 4, 5, 6, 7, 8, 0]).0));
+}
+
+#[test]
+fn parses_and_draws_puzzles() {
+    let puzzle = "1 2 3\n4 5 _\n6 7 8".parse::<Puzzle>().unwrap();
+    assert_eq!(puzzle.tiles(), &[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    assert_eq!(puzzle.to_string(), State::from(puzzle.tiles()).to_string());
+    assert_eq!(format!("{:#}", puzzle), "0 1 2 \n3 4   \n5 6 7 \n");
+
+    assert!(matches!("1 2 3".parse::<Puzzle>(), Err(SolveError::WrongTileCount(3))));
+    assert!(matches!("1 2 3 4 5 0 6 7 7".parse::<Puzzle>(), Err(SolveError::DuplicateTile(7))));
+}
+
+#[test]
+fn indexes_tiles_of_state() {
+    let state = State::from(&[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+
+    assert_eq!(state[0], 1);
+    assert_eq!(state[5], 0);
+    assert_eq!(state.get(8), 8);
+    assert_eq!((0..9).map(|i| state[i]).collect::<Vec<_>>(), state.tiles());
+}
+
+#[test]
+fn annotates_boards_with_scores() {
+    let trace = solve(&[1, 2, 3, 4, 5, 0, 7, 8, 6]).unwrap();
+    let annotated = display_annotated(&trace, &ManhattanDistance, GOAL);
+
+    let boards = annotated.split("\n\n").collect::<Vec<_>>();
+    assert_eq!(boards.len(), 2);
+    assert!(boards[0].starts_with(&State(pack(&[1, 2, 3, 4, 5, 0, 7, 8, 6])).to_string()));
+    assert!(boards[0].ends_with("+---+---+---+\ng = 0, h = 1, f = 1"));
+    assert!(boards[1].ends_with("+---+---+---+\ng = 1, h = 0, f = 1"));
+}
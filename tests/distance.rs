@@ -0,0 +1,175 @@
+#![cfg(feature = "std")]
+
+use std::collections::{HashMap, HashSet};
+
+use superzub::{
+    bfs_levels, bfs_levels_limited, distance, distance_histogram, distance_table,
+    distance_table_with_progress, hardest_states, load_table, pack, rank, save_table, scramble,
+    shuffle_to_distance, solvable_count, solve, solve_with_table, unpack, unrank, SolveError, GOAL,
+};
+
+#[test]
+fn longest_optimal_solution_is_31_moves() {
+    let (moves, states) = hardest_states();
+
+    assert_eq!(moves, 31);
+    assert_eq!(states.len(), 2);
+    assert!(states.contains(&pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1])));
+    assert!(states.contains(&pack(&[6, 4, 7, 8, 5, 0, 3, 2, 1])));
+}
+
+#[test]
+fn histogram_covers_every_solvable_state() {
+    let histogram = distance_histogram();
+
+    assert_eq!(histogram.iter().sum::<u64>() as u128, solvable_count(3));
+    assert_eq!(histogram[..3], [1, 2, 4]);
+    assert_eq!(histogram[31], 2);
+}
+
+#[test]
+fn distance_between_arbitrary_states() {
+    let a = pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1]);
+    let b = pack(&[6, 4, 7, 8, 5, 0, 3, 2, 1]);
+
+    assert_eq!(distance(a, a), Some(0));
+    assert_eq!(distance(a, pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0])), Some(31));
+    assert_eq!(distance(a, b), distance(b, a));
+    assert!(distance(scramble(10, 1), scramble(10, 2)).unwrap() <= 20);
+
+    // two tiles swapped
+    assert_eq!(distance(a, pack(&[6, 8, 7, 2, 5, 4, 3, 0, 1])), None);
+}
+
+#[test]
+fn levels_match_histogram() {
+    let goal = pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+    let levels = bfs_levels(goal);
+
+    assert_eq!(levels.len(), 32);
+    assert_eq!(levels[0], [goal]);
+    assert_eq!(levels.iter().map(Vec::len).sum::<usize>() as u128, solvable_count(3));
+    for (level, &count) in levels.iter().zip(distance_histogram().iter()) {
+        assert_eq!(level.len() as u64, count);
+    }
+
+    let limited = bfs_levels_limited(goal, 5);
+    assert_eq!(limited, levels[..6]);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_table_matches_sequential_one() {
+    assert_eq!(superzub::distance_table_parallel(), superzub::distance_table());
+}
+
+#[test]
+fn saves_and_loads_distance_table() {
+    let table = distance_table();
+    let path = std::env::temp_dir().join(format!("superzub-{}.table", std::process::id()));
+
+    save_table(&table, &path).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 181440 * 5);
+
+    let loaded = load_table(&path).unwrap();
+    assert_eq!(loaded, table);
+
+    let input = [8, 6, 7, 2, 5, 4, 3, 0, 1];
+    let trace = solve_with_table(&input, &loaded).unwrap();
+    assert_eq!(trace.moves_count(), 31);
+    assert_eq!(trace.moves_count(), solve(&input).unwrap().moves_count());
+
+    std::fs::write(&path, [0; 7]).unwrap();
+    assert!(load_table(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_corrupt_tables() {
+    let path = std::env::temp_dir().join(format!("superzub-corrupt-{}.table", std::process::id()));
+    let record = |field: u32, distance: u8| {
+        let mut bytes = field.to_le_bytes().to_vec();
+        bytes.push(distance);
+        bytes
+    };
+    let near = pack(&[1, 2, 3, 4, 5, 6, 7, 0, 8]);
+    let (first, second) = if near < GOAL { (near, GOAL) } else { (GOAL, near) };
+
+    let valid = [record(first, (first != GOAL) as u8), record(second, (second != GOAL) as u8)];
+    let corrupt = [
+        // the blank's position past the board
+        [record(GOAL, 0), record(u32::MAX, 1)].concat(),
+        // out of order
+        [valid[1].clone(), valid[0].clone()].concat(),
+        [record(GOAL, 0), record(GOAL, 0)].concat(),
+        record(near, 1),
+        record(GOAL, 1),
+        [record(first, 40), record(second, 40)].concat(),
+    ];
+
+    std::fs::write(&path, valid.concat()).unwrap();
+    assert_eq!(load_table(&path).unwrap().len(), 2);
+
+    for bytes in &corrupt {
+        std::fs::write(&path, bytes).unwrap();
+        let err = load_table(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData, "{:?}", bytes);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn solving_with_incomplete_table_fails() {
+    let near = pack(&[1, 2, 3, 4, 5, 6, 7, 0, 8]);
+    let table = vec![(GOAL, 0), (near, 1)].into_iter().collect::<HashMap<_, _>>();
+
+    assert_eq!(solve_with_table(&unpack(near), &table).unwrap().moves_count(), 1);
+    assert!(matches!(
+        solve_with_table(&[1, 2, 3, 4, 5, 6, 0, 7, 8], &table),
+        Err(SolveError::IncompleteTable)
+    ));
+
+    // no neighbor a move closer
+    let wrong = vec![(GOAL, 3), (near, 1)].into_iter().collect::<HashMap<_, _>>();
+    assert!(matches!(solve_with_table(&unpack(near), &wrong), Err(SolveError::IncompleteTable)));
+    let zero = vec![(GOAL, 0), (near, 0)].into_iter().collect::<HashMap<_, _>>();
+    assert!(matches!(solve_with_table(&unpack(near), &zero), Err(SolveError::IncompleteTable)));
+}
+
+#[test]
+fn distance_table_reports_progress() {
+    let mut reports = Vec::new();
+    let table = distance_table_with_progress(Some(&mut |progress| reports.push(progress)));
+
+    assert_eq!(table.len(), 181440);
+    assert_eq!(reports.len(), 181440 / superzub::PROGRESS_INTERVAL);
+    assert!(reports.windows(2).all(|w| w[0].depth <= w[1].depth));
+}
+
+#[test]
+fn shuffles_to_exact_distance() {
+    let table = distance_table();
+
+    for d in [0, 1, 5, 20, 31] {
+        for seed in 0..5 {
+            let field = shuffle_to_distance(d, seed);
+            assert_eq!(table[&field], d);
+            assert_eq!(shuffle_to_distance(d, seed), field);
+        }
+    }
+    assert_ne!(shuffle_to_distance(20, 0), shuffle_to_distance(20, 1));
+}
+
+#[test]
+fn ranks_every_state_as_its_tiles() {
+    let mut ranks = HashSet::new();
+
+    for field in bfs_levels(GOAL).into_iter().flatten() {
+        let index = rank(field);
+        assert_eq!(index as u128, superzub::factoriadic::rank(&unpack(field)), "{:#x}", field);
+        assert_eq!(unrank(index), field);
+        assert!(ranks.insert(index));
+    }
+
+    assert_eq!(ranks.len(), 181_440);
+}
@@ -0,0 +1,228 @@
+use std::convert::TryInto;
+
+use superzub::factoriadic::{
+    checked_fact, compose, get_ith, inverse, next_permutation, parity, prev_permutation, rank,
+    rank_of, to_radix_string, unrank, Factoriadic,
+};
+use superzub::{count_inversions, pack, random_solvable, unpack};
+
+#[test]
+fn rank_inverts_unrank() {
+    for &i in &[0, 1, 2, 719, 40320, 123456, 362879] {
+        assert_eq!(rank(&unrank(i, 9)), i);
+    }
+    for i in 0..24 {
+        assert_eq!(rank(&unrank(i, 4)), i);
+    }
+}
+
+#[test]
+fn unrank_is_lexicographic() {
+    assert_eq!(unrank(0, 3), [0, 1, 2]);
+    assert_eq!(unrank(1, 3), [0, 2, 1]);
+    assert_eq!(unrank(2, 3), [1, 0, 2]);
+    assert_eq!(unrank(5, 3), [2, 1, 0]);
+    assert_eq!(get_ith(&[10, 20, 30], 3), [20, 30, 10]);
+}
+
+#[test]
+fn unranks_any_element_type() {
+    let letters = ['a', 'b', 'c'];
+    let all =
+        (0..6).map(|i| get_ith(&letters, i).into_iter().collect::<String>()).collect::<Vec<_>>();
+    assert_eq!(all, ["abc", "acb", "bac", "bca", "cab", "cba"]);
+
+    assert_eq!(get_ith(&["x".to_owned(), "y".to_owned()], 1), ["y", "x"]);
+}
+
+#[test]
+fn rank_of_inverts_get_ith() {
+    let alphabet = [7u64, 3, 9, 1, 4];
+    for i in 0..120 {
+        let perm = get_ith(&alphabet, i);
+        assert_eq!(rank_of(&perm, &alphabet), i);
+        assert_eq!(get_ith(&alphabet, rank_of(&perm, &alphabet)), perm);
+    }
+}
+
+#[test]
+fn states_are_ranked_as_their_tiles() {
+    for &i in &[0, 1, 5000, 181440, 362879] {
+        let field = superzub::unrank(i);
+        assert_eq!(superzub::rank(field), i);
+        assert_eq!(rank(&unpack(field)), i as u128);
+    }
+    assert_eq!(
+        superzub::rank(pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0])),
+        rank(&[1, 2, 3, 4, 5, 6, 7, 8, 0]) as usize
+    );
+}
+
+#[test]
+fn factoriadic_digits() {
+    assert_eq!(Factoriadic::new(0).digits(), [0]);
+    assert_eq!(Factoriadic::new(1).digits(), [0, 1]);
+    assert_eq!(Factoriadic::new(463).digits(), [0, 1, 0, 1, 4, 3]);
+}
+
+#[test]
+fn factorials_of_large_boards() {
+    assert_eq!(checked_fact(0), Some(1));
+    assert_eq!(checked_fact(9), Some(362_880));
+    assert_eq!(checked_fact(25), Some(15_511_210_043_330_985_984_000_000));
+    assert_eq!(checked_fact(34).map(|f| f > u64::MAX as u128), Some(true));
+    assert_eq!(checked_fact(35), None);
+}
+
+#[test]
+fn ranks_permutations_of_25_elements() {
+    let last = (0..25).rev().collect::<Vec<u32>>();
+    assert_eq!(rank(&last), checked_fact(25).unwrap() - 1);
+    assert_eq!(unrank(rank(&last), 25), last);
+}
+
+#[test]
+fn radix_string_starts_with_the_top_digit() {
+    assert_eq!(to_radix_string(0), "0!");
+    assert_eq!(to_radix_string(1), "1:0!");
+    assert_eq!(to_radix_string(463), "3:4:1:0:1:0!");
+    // the last permutation of 3 elements
+    assert_eq!(to_radix_string(5), "2:1:0!");
+}
+
+#[test]
+fn adds_factoriadics_of_different_lengths() {
+    let pairs = [(2, 1000), (1000, 2), (0, 0), (5, 1), (719, 1), (463, 463), (1, 3_628_799)];
+    for &(a, b) in &pairs {
+        let sum = Factoriadic::new(a) + Factoriadic::new(b);
+        assert_eq!(sum.digits(), Factoriadic::new(a + b).digits(), "{} + {}", a, b);
+        assert_eq!(sum.to_u64(), a + b);
+    }
+}
+
+#[test]
+fn to_u64_inverts_new() {
+    for n in (0..5_000_000).step_by(997).chain(0..1000).chain(std::iter::once(u64::MAX / 2)) {
+        assert_eq!(Factoriadic::new(n).to_u64(), n);
+    }
+}
+
+#[test]
+fn to_u64_ignores_leading_zeros() {
+    assert_eq!(Factoriadic::from_digits(vec![0; 25]).unwrap().to_u64(), 0);
+
+    let mut digits = Factoriadic::new(u64::MAX).digits().to_vec();
+    digits.resize(40, 0);
+    assert_eq!(Factoriadic::from_digits(digits).unwrap().to_u64(), u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "overflows u64")]
+fn to_u64_panics_past_u64() {
+    let mut digits = vec![0; 22];
+    digits[21] = 1;
+    let _ = Factoriadic::from_digits(digits).unwrap().to_u64();
+}
+
+#[test]
+fn subtracts_factoriadics() {
+    let pairs = [(1000, 2), (5, 5), (6, 1), (720, 1), (3_628_799, 3_628_798), (463, 0), (0, 0)];
+    for &(a, b) in &pairs {
+        let difference = Factoriadic::new(a) - Factoriadic::new(b);
+        assert_eq!(difference.to_u64(), a - b, "{} - {}", a, b);
+        assert_eq!(difference.digits(), Factoriadic::new(a - b).digits());
+    }
+}
+
+#[test]
+#[should_panic]
+fn subtracting_a_greater_factoriadic_panics() {
+    let _ = Factoriadic::new(2) - Factoriadic::new(1000);
+}
+
+#[test]
+fn orders_like_integers() {
+    let values = [0, 1, 2, 5, 6, 23, 24, 100, 719, 720, 5039, 40320, 362879];
+    for &a in &values {
+        for &b in &values {
+            let (x, y) = (Factoriadic::new(a), Factoriadic::new(b));
+            assert_eq!(x.cmp(&y), a.cmp(&b), "{} vs {}", a, b);
+            assert_eq!(x == y, a == b);
+        }
+    }
+
+    let mut sorted =
+        [720, 3, 40320, 0, 24, 719].iter().map(|&n| Factoriadic::new(n)).collect::<Vec<_>>();
+    sorted.sort();
+    assert_eq!(
+        sorted.iter().map(Factoriadic::to_u64).collect::<Vec<_>>(),
+        [0, 3, 24, 719, 720, 40320]
+    );
+}
+
+#[test]
+fn equal_after_arithmetic() {
+    assert_eq!(
+        Factoriadic::new(1) + Factoriadic::new(0),
+        Factoriadic::new(3) - Factoriadic::new(2)
+    );
+    assert_eq!(Factoriadic::new(6) - Factoriadic::new(6), Factoriadic::new(0));
+}
+
+#[test]
+fn validates_digits() {
+    assert_eq!(Factoriadic::from_digits(vec![0, 1, 2, 3]).unwrap().to_u64(), 23);
+    assert_eq!(Factoriadic::from_digits(vec![0, 1, 0]).unwrap(), Factoriadic::new(1));
+    assert_eq!(Factoriadic::from_digits(vec![]).unwrap(), Factoriadic::new(0));
+
+    assert!(Factoriadic::from_digits(vec![1]).is_err());
+    assert!(Factoriadic::from_digits(vec![0, 1, 3]).is_err());
+}
+
+#[test]
+fn steps_through_permutations() {
+    let mut perm = [0, 1, 2, 3];
+    for i in 1..24 {
+        assert!(next_permutation(&mut perm));
+        assert_eq!(perm.to_vec(), unrank(i, 4));
+    }
+    assert!(!next_permutation(&mut perm));
+    assert_eq!(perm, [0, 1, 2, 3]);
+
+    let mut perm = [3, 2, 1, 0];
+    for i in (0..23).rev() {
+        assert!(prev_permutation(&mut perm));
+        assert_eq!(perm.to_vec(), unrank(i, 4));
+    }
+    assert!(!prev_permutation(&mut perm));
+    assert_eq!(perm, [3, 2, 1, 0]);
+}
+
+#[test]
+fn composing_with_inverse_gives_identity() {
+    let identity = (0..9).collect::<Vec<u32>>();
+
+    for seed in 0..100 {
+        let p = random_solvable(seed);
+        assert_eq!(compose(&p, &inverse(&p)), identity);
+        assert_eq!(compose(&inverse(&p), &p), identity);
+    }
+
+    assert_eq!(compose(&[1, 2, 0], &[0, 2, 1]), [1, 0, 2]);
+    assert_eq!(inverse(&[1, 2, 0]), [2, 0, 1]);
+}
+
+#[test]
+fn parity_matches_inversions() {
+    for i in (0..362880).step_by(101) {
+        let perm = unrank(i, 9);
+        let field: [u32; 9] = perm.as_slice().try_into().unwrap();
+        let tiles = perm.iter().copied().filter(|&x| x != 0).collect::<Vec<_>>();
+
+        assert_eq!(parity(&tiles), count_inversions(&field).is_multiple_of(2));
+    }
+
+    assert!(parity(&[0, 1, 2]));
+    assert!(!parity(&[1, 0, 2]));
+    assert!(parity(&[2, 0, 1]));
+}
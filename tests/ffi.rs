@@ -0,0 +1,46 @@
+#![cfg(feature = "ffi")]
+
+use superzub::ffi::{
+    superzub_scramble, superzub_solve, SUPERZUB_INVALID_INPUT, SUPERZUB_MAX_MOVES,
+    SUPERZUB_NULL_POINTER, SUPERZUB_OK, SUPERZUB_UNSOLVABLE,
+};
+use superzub::{scramble, unpack};
+
+fn call(input: &[u32; 9]) -> (i32, Vec<u8>) {
+    let mut moves = [0xff; SUPERZUB_MAX_MOVES];
+    let mut len = 0;
+    let code = unsafe { superzub_solve(input.as_ptr(), moves.as_mut_ptr(), &mut len) };
+    (code, moves[..len].to_vec())
+}
+
+#[test]
+fn solves_through_c_interface() {
+    // blank moves right, then down
+    assert_eq!(call(&[1, 2, 3, 4, 0, 5, 7, 8, 6]), (SUPERZUB_OK, vec![3, 1]));
+    assert_eq!(call(&[1, 2, 3, 4, 5, 6, 7, 8, 0]), (SUPERZUB_OK, vec![]));
+
+    let (code, moves) = call(&[8, 6, 7, 2, 5, 4, 3, 0, 1]);
+    assert_eq!(code, SUPERZUB_OK);
+    assert_eq!(moves.len(), SUPERZUB_MAX_MOVES);
+    assert!(moves.iter().all(|&direction| direction < 4));
+}
+
+#[test]
+fn reports_errors_through_c_interface() {
+    assert_eq!(call(&[1, 2, 3, 4, 5, 6, 8, 7, 0]).0, SUPERZUB_UNSOLVABLE);
+    assert_eq!(call(&[1, 2, 3, 4, 5, 6, 7, 7, 0]).0, SUPERZUB_INVALID_INPUT);
+
+    let mut len = 0;
+    let code = unsafe { superzub_solve(std::ptr::null(), std::ptr::null_mut(), &mut len) };
+    assert_eq!(code, SUPERZUB_NULL_POINTER);
+}
+
+#[test]
+fn scrambles_through_c_interface() {
+    let mut tiles = [0; 9];
+    assert_eq!(unsafe { superzub_scramble(20, 7, tiles.as_mut_ptr()) }, SUPERZUB_OK);
+    assert_eq!(tiles, unpack(scramble(20, 7)));
+    assert_eq!(call(&tiles).0, SUPERZUB_OK);
+
+    assert_eq!(unsafe { superzub_scramble(20, 7, std::ptr::null_mut()) }, SUPERZUB_NULL_POINTER);
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "std")]
+
+use superzub::{
+    astar, astar_stats, astar_weighted, bfs, idastar, manhattan, neighbors, pack, random_solvable,
+    verify, Heuristic, LinearConflict, ManhattanDistance, MisplacedTiles, SolveError, Zero,
+};
+
+const GOAL: [u32; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+// puzzles with their optimal solution lengths
+#[rustfmt::skip]
+const PUZZLES: [([u32; 9], u32); 5] = [
+    ([1, 2, 3, 4, 5, 6, 7, 8, 0], 0),
+    ([1, 2, 3, 4, 5, 6, 7, 0, 8], 1),
+    ([1, 2, 3, 4, 5, 0, 6, 7, 8], 13),
+    ([8, 6, 7, 2, 5, 4, 3, 0, 1], 31),
+    ([6, 4, 7, 8, 5, 0, 3, 2, 1], 31),
+];
+
+fn assert_admissible(heuristic: impl Heuristic) {
+    let goal = pack(&GOAL);
+    for &(input, moves) in &PUZZLES {
+        let input = pack(&input);
+        assert!(heuristic.estimate(input, goal) <= moves, "{:?}", input);
+        assert_eq!(heuristic.estimate(goal, goal), 0);
+    }
+}
+
+#[test]
+fn optimal_lengths_are_right() {
+    let goal = pack(&GOAL);
+    for &(input, moves) in &PUZZLES {
+        let trace = bfs(pack(&input), goal).unwrap();
+        assert_eq!(trace.directions().len() as u32, moves, "{:?}", input);
+    }
+}
+
+#[test]
+fn manhattan_distance_is_admissible() {
+    assert_admissible(ManhattanDistance);
+}
+
+#[test]
+fn misplaced_tiles_is_admissible() {
+    assert_admissible(MisplacedTiles);
+}
+
+#[test]
+fn linear_conflict_is_admissible() {
+    assert_admissible(LinearConflict);
+}
+
+#[test]
+fn zero_is_admissible() {
+    assert_admissible(Zero);
+}
+
+#[test]
+fn astar_is_optimal_with_every_heuristic() {
+    let goal = pack(&GOAL);
+    let heuristics: [&dyn Heuristic; 4] =
+        [&ManhattanDistance, &LinearConflict, &MisplacedTiles, &Zero];
+
+    for heuristic in &heuristics {
+        for &(input, moves) in &PUZZLES[..3] {
+            let trace = astar(pack(&input), goal, heuristic).unwrap();
+            assert_eq!(trace.directions().len() as u32, moves, "{:?}", input);
+        }
+    }
+}
+
+#[test]
+fn manhattan_distance_of_known_state() {
+    // 1 and 2 are one cell off horizontally, 5 and 8 one cell off vertically
+    let field = pack(&[2, 1, 3, 4, 8, 6, 7, 5, 0]);
+    assert_eq!(ManhattanDistance.estimate(field, pack(&GOAL)), 4);
+    assert_eq!(MisplacedTiles.estimate(field, pack(&GOAL)), 4);
+}
+
+#[test]
+fn linear_conflict_of_known_state() {
+    // 1 and 2 are swapped in their goal row and 5 and 8 in their goal column
+    let field = pack(&[2, 1, 3, 4, 8, 6, 7, 5, 0]);
+    assert_eq!(LinearConflict.estimate(field, pack(&GOAL)), 8);
+
+    // three reversed tiles take two of them stepping aside, not three
+    let field = pack(&[3, 2, 1, 4, 5, 6, 7, 8, 0]);
+    assert_eq!(LinearConflict.estimate(field, pack(&GOAL)), 4 + 4);
+}
+
+#[test]
+fn linear_conflict_expands_fewer_states_than_manhattan() {
+    let goal = pack(&GOAL);
+    let (mut manhattan_expanded, mut conflict_expanded) = (0, 0);
+
+    for seed in 0..10 {
+        let input = pack(&random_solvable(seed));
+        let (by_manhattan, manhattan) = astar_stats(input, goal, ManhattanDistance).unwrap();
+        let (by_conflict, conflict) = astar_stats(input, goal, LinearConflict).unwrap();
+
+        assert_eq!(by_manhattan.directions().len(), by_conflict.directions().len());
+        assert!(LinearConflict.estimate(input, goal) >= ManhattanDistance.estimate(input, goal));
+
+        manhattan_expanded += manhattan.expanded;
+        conflict_expanded += conflict.expanded;
+    }
+
+    assert!(conflict_expanded < manhattan_expanded, "{} {}", conflict_expanded, manhattan_expanded);
+}
+
+#[test]
+fn manhattan_distance_near_goal() {
+    let goal = pack(&GOAL);
+    assert_eq!(manhattan(goal, goal), 0);
+
+    for (_, field) in neighbors(goal) {
+        assert_eq!(manhattan(field, goal), 1);
+    }
+    assert_eq!(manhattan(pack(&[1, 2, 3, 4, 5, 0, 6, 7, 8]), goal), 5);
+}
+
+#[test]
+fn weighted_astar_trades_length_for_speed() {
+    let goal = pack(&GOAL);
+    let inputs = (0..20).map(|seed| pack(&random_solvable(seed))).collect::<Vec<_>>();
+
+    let mut longer = 0;
+    for &input in &inputs {
+        let optimal = astar(input, goal, ManhattanDistance).unwrap().moves_count();
+        assert_eq!(
+            astar_weighted(input, goal, ManhattanDistance, 1.0).unwrap().moves_count(),
+            optimal
+        );
+
+        let trace = astar_weighted(input, goal, ManhattanDistance, 3.0).unwrap();
+        assert!(verify(&trace).is_ok());
+        assert_eq!(trace.states().last(), Some(goal));
+        assert!(trace.moves_count() >= optimal);
+        if trace.moves_count() > optimal {
+            longer += 1;
+        }
+    }
+    assert!(longer > 0);
+}
+
+#[test]
+fn astar_breaks_ties_the_same_way_every_time() {
+    let (input, goal) = (pack(&PUZZLES[3].0), pack(&GOAL));
+    let trace = astar(input, goal, ManhattanDistance).unwrap();
+
+    let moves = trace.directions().iter().map(ToString::to_string).collect::<String>();
+    assert_eq!(moves, "LURURDDLULURRDDLLUURRDLDLUURDRD");
+    assert_eq!(astar(input, goal, ManhattanDistance).unwrap(), trace);
+}
+
+#[test]
+fn astar_reports_unreachable_goals() {
+    let input = pack(&[1, 2, 3, 4, 5, 6, 8, 7, 0]);
+    let goal = pack(&GOAL);
+
+    assert!(matches!(astar(input, goal, ManhattanDistance), Err(SolveError::Unsolvable { .. })));
+    assert!(matches!(astar_stats(input, goal, Zero), Err(SolveError::Unsolvable { .. })));
+    assert!(astar_weighted(input, goal, ManhattanDistance, 2.0).is_err());
+}
+
+#[test]
+fn idastar_gives_up_on_unreachable_goals() {
+    let input = pack(&[1, 2, 3, 4, 5, 6, 8, 7, 0]);
+    assert!(idastar(input, pack(&GOAL), ManhattanDistance).is_none());
+    assert_eq!(idastar(pack(&GOAL), pack(&GOAL), ManhattanDistance).unwrap().moves_count(), 0);
+}
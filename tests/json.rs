@@ -0,0 +1,123 @@
+#![cfg(feature = "json")]
+
+use std::convert::TryInto;
+
+use superzub::json::{self, Value};
+use superzub::{solve, solve_alphabet, SolveError, State, Trace};
+
+fn tiles(s: &str) -> Result<[u32; 9], SolveError> {
+    (&Value::parse(s).unwrap()).try_into()
+}
+
+#[test]
+fn reads_puzzles_from_arrays() {
+    let input = tiles("[1, 2, 3, 4, 5, 6, 7, 0, 8]").unwrap();
+    assert_eq!(input, [1, 2, 3, 4, 5, 6, 7, 0, 8]);
+    assert_eq!(solve(&input).unwrap().moves_count(), 1);
+
+    assert!(matches!(tiles("[1, 2, 3]"), Err(SolveError::WrongTileCount(3))));
+    assert!(matches!(tiles("[1, 2, 3, 4, 5, 6, 7, 8, 8]"), Err(SolveError::MissingBlank)));
+    assert!(matches!(tiles("[1, 2, 3, 4, 5, 6, 7, 9, 0]"), Err(SolveError::OutOfRange(9))));
+    assert!(matches!(tiles("[1, 2, 3, 4, 5, 6, 7, \"8\", 0]"), Err(SolveError::InvalidJson(_))));
+    assert!(matches!(tiles("[1, 2, 3, 4, 5, 6, 7, 8.5, 0]"), Err(SolveError::InvalidJson(_))));
+    assert!(matches!(tiles("{\"tiles\": []}"), Err(SolveError::InvalidJson(_))));
+}
+
+#[test]
+fn round_trips_states() {
+    let state = State::from(&[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    let text = json::to_string(&state);
+
+    assert_eq!(text, "[1,2,3,4,5,0,6,7,8]");
+    assert_eq!(json::from_str::<State>(&text).unwrap(), state);
+}
+
+#[test]
+fn limits_nesting() {
+    assert!(Value::parse(&format!("{}{}", "[".repeat(100), "]".repeat(100))).is_ok());
+    assert!(matches!(Value::parse(&"[".repeat(200_000)), Err(SolveError::InvalidJson(_))));
+    assert!(matches!(Value::parse(&"{\"a\":".repeat(200_000)), Err(SolveError::InvalidJson(_))));
+}
+
+fn invalid(s: &str) -> bool {
+    matches!(Value::parse(s), Err(SolveError::InvalidJson(_)))
+}
+
+#[test]
+fn parses_values() {
+    let value = Value::parse(r#" {"a": [null, true, false, -1.5e2, 0, "x"], "b": {}} "#).unwrap();
+    let a = value.get("a").and_then(Value::as_array).unwrap();
+
+    assert_eq!(a[0], Value::Null);
+    assert_eq!(a[1], Value::Bool(true));
+    assert_eq!(a[2], Value::Bool(false));
+    assert_eq!(a[3], Value::Number(-150.0));
+    assert_eq!(a[4].as_u32(), Some(0));
+    assert_eq!(a[5], Value::String("x".to_owned()));
+    assert_eq!(value.get("b"), Some(&Value::Object(vec![])));
+    assert_eq!(value.get("c"), None);
+
+    assert!(invalid("[1, 2"));
+    assert!(invalid("[1 2]"));
+    assert!(invalid("[1] x"));
+    assert!(invalid("nul"));
+    assert!(invalid(""));
+}
+
+#[test]
+fn follows_the_number_grammar() {
+    for &(text, value) in &[("0", 0.0), ("-0", 0.0), ("10", 10.0), ("0.25", 0.25), ("1E+2", 100.0)]
+    {
+        assert_eq!(Value::parse(text).unwrap(), Value::Number(value), "{}", text);
+    }
+
+    for text in &["01", "-", "+1", "1.", ".5", "1e", "1e+", "--1", "1.2.3", "[01]"] {
+        assert!(invalid(text), "{}", text);
+    }
+}
+
+#[test]
+fn unescapes_strings() {
+    let value = Value::parse(r#""a\"\\\/\n\té😀""#).unwrap();
+    assert_eq!(value, Value::String("a\"\\/\n\té😀".to_owned()));
+
+    // a high surrogate has to be followed by a low one
+    assert!(invalid(r#""\ud800A""#));
+    assert!(invalid(r#""\ud800""#));
+    assert!(invalid(r#""\udc00""#));
+    assert!(invalid(r#""\x""#));
+    assert!(invalid(r#""open"#));
+}
+
+#[test]
+fn writes_what_it_reads() {
+    let text = r#"{"s":"a\"b\\c\nd\u0001","n":[1,-2.5,null,true]}"#;
+    assert_eq!(Value::parse(text).unwrap().to_string(), text);
+}
+
+#[test]
+fn round_trips_traces() {
+    let trace = solve(&[1, 2, 3, 4, 0, 5, 7, 8, 6]).unwrap();
+    let text = json::to_string(&trace);
+
+    assert!(text.starts_with(r#"{"trace":[[1,2,3,4,0,5,7,8,6],"#));
+    assert_eq!(json::from_str::<Trace>(&text).unwrap(), trace);
+    assert!(json::from_str::<Trace>(r#"{"states":[]}"#).is_err());
+
+    // states that aren't a move apart
+    let jumped = r#"{"trace":[[1,2,3,4,0,5,7,8,6],[1,2,3,4,5,6,7,8,0]]}"#;
+    assert!(matches!(json::from_str::<Trace>(jumped), Err(SolveError::IllegalMove(1))));
+}
+
+#[test]
+fn round_trips_alphabets() {
+    let alphabet = [" ", "a", "b", "c", "d", "e", "f", "g", "h"];
+    let trace = solve_alphabet(&["a", "b", "c", "d", " ", "e", "g", "h", "f"], &alphabet).unwrap();
+    let text = json::to_string(&trace);
+
+    assert!(text.ends_with(r#""alphabet":[" ","a","b","c","d","e","f","g","h"]}"#));
+    assert_eq!(json::from_str::<Trace>(&text).unwrap(), trace);
+
+    assert!(json::from_str::<Trace>(r#"{"trace":[],"alphabet":["a"]}"#).is_err());
+    assert!(json::from_str::<Trace>(r#"{"trace":[],"alphabet":[1,2,3,4,5,6,7,8,9]}"#).is_err());
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "std")]
+
+use superzub::pdb::PatternDatabase;
+use superzub::Board;
+
+fn scramble<const R: usize, const C: usize>(moves: usize, mut seed: u64) -> Vec<u32> {
+    let mut board = Board::<R, C>::goal();
+    for _ in 0..moves {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        board = match seed >> 62 {
+            0 => board.up(),
+            1 => board.down(),
+            2 => board.left(),
+            _ => board.right(),
+        };
+    }
+
+    let blank = board.get_blank_pos();
+    (0..R * C).map(|i| if i == blank { 0 } else { board.get_tile(i) + 1 }).collect()
+}
+
+#[test]
+fn pattern_database_is_admissible() {
+    let pdb = PatternDatabase::<3, 3>::generate(&[&[1, 2, 3, 4], &[5, 6, 7, 8]]);
+    assert_eq!(pdb.estimate(Board::goal()), 0);
+
+    for seed in 0..20 {
+        let input = scramble::<3, 3>(40, seed);
+        let board = Board::<3, 3>::pack(&input).unwrap();
+        let optimal = Board::<3, 3>::solve(&input).unwrap().len() as u32 - 1;
+
+        assert!(board.manhattan() <= pdb.estimate(board), "{:?}", input);
+        assert!(pdb.estimate(board) <= optimal, "{:?}", input);
+
+        let trace = Board::<3, 3>::solve_with(&input, |board| pdb.estimate(board)).unwrap();
+        assert_eq!(trace.len() as u32 - 1, optimal, "{:?}", input);
+    }
+}
+
+#[test]
+fn solves_15_puzzle_with_pattern_database() {
+    let pdb = PatternDatabase::<4, 4>::generate(&[
+        &[1, 2, 3],
+        &[4, 7, 8],
+        &[5, 6, 9],
+        &[10, 13, 14],
+        &[11, 12, 15],
+    ]);
+
+    for seed in 0..5 {
+        let input = scramble::<4, 4>(60, seed);
+        let trace = Board::<4, 4>::solve_with(&input, |board| pdb.estimate(board)).unwrap();
+        assert_eq!(trace.last(), Some(&Board::goal()));
+        assert!(pdb.estimate(trace[0]) < trace.len() as u32);
+    }
+}
+
+#[test]
+fn saves_and_loads_pattern_database() {
+    let pdb = PatternDatabase::<2, 3>::generate(&[&[1, 2, 3], &[4, 5]]);
+    let path = std::env::temp_dir().join(format!("superzub-{}.pdb", std::process::id()));
+
+    pdb.save(&path).unwrap();
+    assert_eq!(PatternDatabase::<2, 3>::load(&path).unwrap(), pdb);
+    assert!(PatternDatabase::<3, 2>::load(&path).is_err());
+
+    std::fs::write(&path, b"SZPDB\x02\x03\x01\x02\x01").unwrap();
+    assert!(PatternDatabase::<2, 3>::load(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_overlapping_groups() {
+    let pdb = PatternDatabase::<2, 3>::generate(&[&[1, 2, 3], &[4, 5]]);
+    let path = std::env::temp_dir().join(format!("superzub-overlap-{}.pdb", std::process::id()));
+    pdb.save(&path).unwrap();
+
+    // the magic, the shape, the number of groups, then the first group's size, tiles and 6 * 5 * 4 costs,
+    // followed by the size of the second group and its first tile, 4, which becomes a second 3
+    let mut bytes = std::fs::read(&path).unwrap();
+    let second = 5 + 3 + 1 + 3 + 120 + 1;
+    assert_eq!(bytes[second], 4);
+    bytes[second] = 3;
+    std::fs::write(&path, &bytes).unwrap();
+
+    let err = PatternDatabase::<2, 3>::load(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "std")]
+
+use superzub::{
+    bfs, bidirectional_bfs, pack, random_solvable, scramble, solve, solve_with, unpack, verify,
+    Algorithm, SolveConfig, SolveError, Trace, GOAL,
+};
+
+// Properties every solution has to satisfy, checked on seeded random puzzles so that failures reproduce.
+
+const SEEDS: u64 = 20;
+
+fn puzzles() -> impl Iterator<Item = [u32; 9]> {
+    let scrambled = (0..SEEDS).map(|seed| unpack(scramble(5 + seed as usize, seed)));
+    let random = (0..SEEDS).map(random_solvable);
+    scrambled.chain(random)
+}
+
+fn assert_solves(trace: &Trace, input: &[u32; 9]) {
+    assert_eq!(trace.states().next(), Some(pack(input)), "{:?}", input);
+    assert_eq!(trace.states().last(), Some(GOAL), "{:?}", input);
+    assert!(verify(trace).is_ok(), "{:?}", input);
+}
+
+#[test]
+fn traces_go_from_the_input_to_the_goal() {
+    for input in puzzles() {
+        assert_solves(&solve(&input).unwrap(), &input);
+    }
+}
+
+#[test]
+fn every_search_finds_the_shortest_solution() {
+    for input in puzzles() {
+        let field = pack(&input);
+        let reference = bfs(field, GOAL).unwrap().moves_count();
+
+        for &algorithm in &[Algorithm::AStar, Algorithm::IdaStar] {
+            let cfg = SolveConfig { algorithm, ..SolveConfig::default() };
+            let trace = solve_with(&input, &cfg).unwrap();
+            assert_solves(&trace, &input);
+            assert_eq!(trace.moves_count(), reference, "{:?} {:?}", algorithm, input);
+        }
+
+        let trace = bidirectional_bfs(field, GOAL).unwrap();
+        assert_solves(&trace, &input);
+        assert_eq!(trace.moves_count(), reference, "{:?}", input);
+    }
+}
+
+#[test]
+fn swapping_two_tiles_makes_puzzles_unsolvable() {
+    for mut input in puzzles() {
+        let mut tiles = (0..9).filter(|&i| input[i] != 0);
+        let (a, b) = (tiles.next().unwrap(), tiles.next_back().unwrap());
+        input.swap(a, b);
+
+        assert!(matches!(solve(&input), Err(SolveError::Unsolvable { .. })), "{:?}", input);
+    }
+}
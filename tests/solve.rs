@@ -0,0 +1,563 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use superzub::{
+    apply, as_2d, batch_stats, bfs, bfs_stats, bfs_stats_with_capacity, bidirectional_bfs,
+    blank_coords, decode, down, encode, from_2d, goal_array, goal_blank_first, goal_with_blank,
+    hint, is_solved, is_solved_to, left, make_solvable, pack, random_solvable, replay, right,
+    scramble, solve, solve_alphabet, solve_batch, solve_iter, solve_to, solve_to_any, solve_with,
+    solve_with_progress, tile_coords, unpack, up, verify, Algorithm, Direction, Puzzle,
+    SearchProgress, SolveConfig, SolveError, State, Trace, BFS_CAPACITY, GOAL,
+};
+
+#[test]
+fn reports_inversions_of_unsolvable_puzzle() {
+    // 8 and 7 swapped
+    let input = [1, 2, 3, 4, 5, 6, 8, 7, 0];
+    assert!(matches!(solve(&input), Err(SolveError::Unsolvable { inversions: 1 })));
+
+    // swapping them back leaves no inversions and a solved puzzle
+    assert_eq!(solve(&[1, 2, 3, 4, 5, 6, 7, 8, 0]).unwrap().states().count(), 1);
+}
+
+#[test]
+fn reports_alphabet_mismatch() {
+    let alphabet = ['_', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+    match solve_alphabet(&['a', 'b', 'c', 'd', 'e', 'f', 'g', 'g', 'x'], &alphabet) {
+        Err(SolveError::AlphabetMismatch { missing, extra }) => {
+            assert_eq!(missing, ["_", "h"]);
+            assert_eq!(extra, ["g", "x"]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn classifies_invalid_tiles() {
+    assert!(matches!(solve(&[1, 2, 3, 4, 5, 6, 7, 8, 9]), Err(SolveError::OutOfRange(9))));
+    assert!(matches!(solve(&[1, 2, 3, 4, 5, 6, 7, 8, 8]), Err(SolveError::MissingBlank)));
+    assert!(matches!(solve(&[1, 2, 3, 4, 5, 0, 0, 7, 8]), Err(SolveError::DuplicateBlank)));
+    assert!(matches!(solve(&[1, 2, 3, 4, 5, 0, 6, 7, 7]), Err(SolveError::DuplicateTile(7))));
+
+    // an out of range value is reported first, even though it leaves the board without a blank tile
+    assert!(matches!(solve(&[1, 2, 3, 4, 5, 6, 7, 7, 9]), Err(SolveError::OutOfRange(9))));
+}
+
+#[test]
+fn solves_relabeled_puzzle() {
+    // 1 to 9 with 9 as the blank
+    let alphabet = [9, 1, 2, 3, 4, 5, 6, 7, 8];
+    let trace = solve_alphabet(&[1, 2, 3, 4, 5, 6, 7, 9, 8], &alphabet).unwrap();
+    assert_eq!(trace.states().count(), 2);
+}
+
+#[test]
+fn bidirectional_bfs_finds_optimal_solutions() {
+    let goal = pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+    let hardest = pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1]);
+    let inputs = [goal, scramble(1, 0), scramble(10, 1), scramble(25, 2), scramble(60, 3), hardest];
+
+    for &input in &inputs {
+        let trace = bidirectional_bfs(input, goal).unwrap();
+
+        assert!(verify(&trace).is_ok());
+        assert_eq!(trace.states().next(), Some(input));
+        assert_eq!(trace.states().last(), Some(goal));
+        assert_eq!(trace.directions().len(), bfs(input, goal).unwrap().moves_count());
+    }
+}
+
+#[test]
+fn solves_batches_optimally() {
+    let mut inputs = (0..20).map(random_solvable).collect::<Vec<_>>();
+    inputs.push([1, 2, 3, 4, 5, 6, 8, 7, 0]);
+    inputs.push([1, 2, 3, 4, 5, 6, 7, 7, 0]);
+
+    let results = solve_batch(&inputs);
+    let goal = pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+
+    let mut lengths = Vec::new();
+    for (input, result) in inputs.iter().zip(&results).take(20) {
+        let trace = result.as_ref().unwrap();
+        assert!(verify(trace).is_ok());
+        assert_eq!(trace.states().next(), Some(pack(input)));
+        assert_eq!(trace.directions().len(), bfs(pack(input), goal).unwrap().moves_count());
+        lengths.push(trace.directions().len());
+    }
+
+    let stats = batch_stats(&results);
+    assert_eq!((stats.solved, stats.unsolvable, stats.invalid), (20, 1, 1));
+    assert_eq!(stats.max_length, *lengths.iter().max().unwrap());
+    assert_eq!(stats.average_length, lengths.iter().sum::<usize>() as f64 / 20.0);
+}
+
+#[test]
+fn converts_between_rows_and_reading_order() {
+    #[rustfmt::skip]
+    let grid = [
+        [1, 2, 3],
+        [4, 5, 0],
+        [6, 7, 8]];
+
+    assert_eq!(from_2d(grid), [1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    assert_eq!(as_2d(pack(&from_2d(grid))), grid);
+}
+
+#[test]
+fn solves_with_config() {
+    let input = [8, 6, 7, 2, 5, 4, 3, 0, 1];
+
+    let limited = SolveConfig { max_nodes: Some(1000), ..SolveConfig::default() };
+    assert!(matches!(solve_with(&input, &limited), Err(SolveError::LimitExceeded)));
+
+    let astar = SolveConfig { algorithm: Algorithm::AStar, ..limited };
+    assert!(matches!(solve_with(&input, &astar), Err(SolveError::LimitExceeded)));
+
+    let goal = [1, 2, 3, 4, 5, 6, 7, 0, 8];
+    let to_goal = SolveConfig { goal: Some(goal), ..SolveConfig::default() };
+    let trace = solve_with(&[1, 2, 3, 4, 5, 6, 7, 8, 0], &to_goal).unwrap();
+    assert_eq!(trace.states().last(), Some(pack(&goal)));
+
+    assert_eq!(solve_with(&input, &SolveConfig::default()).unwrap().directions().len(), 31);
+}
+
+#[test]
+fn solved_input_has_no_moves() {
+    let goal = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+    for &algorithm in &[Algorithm::Bfs, Algorithm::AStar, Algorithm::IdaStar] {
+        let trace =
+            solve_with(&goal, &SolveConfig { algorithm, ..SolveConfig::default() }).unwrap();
+        assert!(trace.directions().is_empty());
+        assert_eq!(trace.states().collect::<Vec<_>>(), [pack(&goal)]);
+    }
+
+    let (_, stats) = bfs_stats(pack(&goal), pack(&goal)).unwrap();
+    assert_eq!(stats.expanded, 0);
+}
+
+#[test]
+fn counts_states_and_moves() {
+    let trace = solve(&[1, 2, 3, 4, 5, 0, 6, 7, 8]).unwrap();
+    assert_eq!(trace.len(), 14);
+    assert_eq!(trace.moves_count(), 13);
+    assert_eq!(trace.moves_count(), trace.directions().len());
+    assert!(!trace.is_empty());
+}
+
+#[test]
+fn encodes_fields_in_a_line() {
+    let field = pack(&[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    assert_eq!(encode(field), "12345_678");
+    assert_eq!(decode("123450678").unwrap(), field);
+
+    for seed in 0..100 {
+        let field = scramble(30, seed);
+        assert_eq!(decode(&encode(field)).unwrap(), field);
+    }
+
+    assert!(matches!(decode("12345_67"), Err(SolveError::WrongTileCount(8))));
+    assert!(matches!(decode("12345_67x"), Err(SolveError::InvalidToken(_))));
+    assert!(matches!(decode("12345_677"), Err(SolveError::DuplicateTile(7))));
+}
+
+#[test]
+fn bfs_reports_unreachable_input() {
+    // 7 and 8 swapped, so the goal is in the other half of the state space
+    let input = pack(&[1, 2, 3, 4, 5, 6, 8, 7, 0]);
+    let goal = pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+
+    assert!(matches!(bfs(input, goal), Err(SolveError::Unsolvable { inversions: 1 })));
+    assert!(matches!(
+        bidirectional_bfs(input, goal),
+        Err(SolveError::Unsolvable { inversions: 1 })
+    ));
+}
+
+#[test]
+fn writes_solutions_as_json() {
+    let trace = solve(&[1, 2, 3, 4, 5, 6, 0, 7, 8]).unwrap();
+    assert_eq!(
+        trace.solution_json(),
+        r#"{"moves":["R","R"],"states":[[1,2,3,4,5,6,0,7,8],[1,2,3,4,5,6,7,0,8],[1,2,3,4,5,6,7,8,0]]}"#
+    );
+    assert_eq!(
+        solve(&goal_array()).unwrap().solution_json(),
+        r#"{"moves":[],"states":[[1,2,3,4,5,6,7,8,0]]}"#
+    );
+}
+
+#[test]
+fn bfs_rejects_corrupt_output() {
+    // every tile is 8, which would rank past the last of the 9! arrangements
+    let corrupt = 0x07FF_FFFF | (8 << 27);
+
+    assert!(matches!(bfs(GOAL, corrupt), Err(SolveError::CorruptState(0))));
+}
+
+#[test]
+fn solves_towards_blank_in_center() {
+    let center = goal_with_blank(4);
+    assert_eq!(center, [1, 2, 3, 4, 0, 5, 6, 7, 8]);
+    assert_eq!(goal_with_blank(8), [1, 2, 3, 4, 5, 6, 7, 8, 0]);
+
+    let trace = solve_to(&[1, 2, 3, 4, 5, 6, 7, 8, 0], &center).unwrap();
+    assert!(verify(&trace).is_ok());
+    assert_eq!(trace.states().last(), Some(pack(&center)));
+
+    // the tiles keep their order whichever cell the blank ends up in
+    let input = [1, 2, 3, 4, 5, 6, 8, 7, 0];
+    assert!(matches!(solve_to(&input, &center), Err(SolveError::Unsolvable { .. })));
+}
+
+#[test]
+fn yields_states_one_at_a_time() {
+    let input = [1, 2, 3, 4, 5, 0, 6, 7, 8];
+    let trace = solve(&input).unwrap();
+
+    let mut states = solve_iter(&input).unwrap();
+    assert_eq!(states.next(), Some(pack(&input)));
+    assert_eq!(
+        states.take(2).collect::<Vec<_>>(),
+        trace.states().skip(1).take(2).collect::<Vec<_>>()
+    );
+    assert_eq!(solve_iter(&input).unwrap().count(), trace.len());
+
+    assert!(matches!(solve_iter(&[1, 2, 3, 4, 5, 6, 8, 7, 0]), Err(SolveError::Unsolvable { .. })));
+}
+
+#[test]
+fn converts_slices_to_puzzles() {
+    let tiles = vec![1, 2, 3, 4, 5, 0, 6, 7, 8];
+    let puzzle = Puzzle::try_from(tiles.as_slice()).unwrap();
+    assert_eq!(puzzle.tiles(), &[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    assert!(solve(puzzle.tiles()).is_ok());
+
+    assert!(matches!(Puzzle::try_from(&tiles[..8]), Err(SolveError::WrongTileCount(8))));
+    assert!(matches!(
+        Puzzle::try_from(&[1, 2, 3, 4, 5, 0, 6, 7, 7][..]),
+        Err(SolveError::DuplicateTile(7))
+    ));
+}
+
+#[test]
+fn describes_errors() {
+    fn solve_boxed(input: &[u32; 9]) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(solve(input)?.moves_count())
+    }
+
+    let err = solve_boxed(&[1, 2, 3, 4, 5, 6, 8, 7, 0]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "puzzle is unsolvable: 1 inversions don't match the parity of the goal"
+    );
+
+    let err = solve(&[1, 2, 3, 4, 5, 0, 0, 7, 8]).unwrap_err();
+    assert_eq!(err.to_string(), "there is more than one blank tile 0");
+
+    let alphabet = ['_', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+    let err =
+        solve_alphabet(&['a', 'b', 'c', 'd', 'e', 'f', 'g', 'g', 'x'], &alphabet).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "input is not a valid permutation of the tiles, missing: _ h, unexpected: g x"
+    );
+}
+
+#[test]
+fn reports_search_progress() {
+    // A* expands more than PROGRESS_INTERVAL states for this one, unlike for many of the 31-move puzzles
+    let input = [5, 6, 3, 8, 0, 2, 7, 4, 1];
+
+    for &algorithm in &[Algorithm::Bfs, Algorithm::AStar] {
+        let cfg = SolveConfig { algorithm, ..SolveConfig::default() };
+        let mut reports = Vec::<SearchProgress>::new();
+        let trace = solve_with_progress(&input, &cfg, Some(&mut |p| reports.push(p))).unwrap();
+
+        assert_eq!(trace.moves_count(), 30);
+        assert!(!reports.is_empty());
+        assert!(reports.windows(2).all(|w| w[0].expanded < w[1].expanded));
+        assert!(reports.iter().all(|p| p.depth <= 30 && p.frontier > 0));
+    }
+}
+
+#[test]
+fn finds_coordinates_of_tiles() {
+    let field = pack(&[1, 2, 3, 4, 5, 0, 6, 7, 8]);
+    assert_eq!(blank_coords(field), (1, 2));
+    assert_eq!(tile_coords(field, 0), (1, 2));
+    assert_eq!(tile_coords(field, 1), (0, 0));
+    assert_eq!(tile_coords(field, 6), (2, 0));
+    assert_eq!(tile_coords(field, 8), (2, 2));
+}
+
+#[test]
+fn solves_to_nearest_goal() {
+    let goal = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+    let rotated = [0, 8, 7, 6, 5, 4, 3, 2, 1];
+    let goals = [goal, rotated];
+
+    for input in
+        [[1, 2, 3, 4, 5, 0, 7, 8, 6], [8, 0, 7, 6, 5, 4, 3, 2, 1], [8, 6, 7, 2, 5, 4, 3, 0, 1]]
+    {
+        let trace = solve_to_any(&input, &goals).unwrap();
+        let nearest = goals.iter().map(|goal| solve_to(&input, goal).unwrap().moves_count()).min();
+
+        assert!(verify(&trace).is_ok());
+        assert_eq!(Some(trace.moves_count()), nearest);
+        let end = trace.states().last().unwrap();
+        assert!(end == pack(&goal) || end == pack(&rotated));
+        assert_eq!(solve_to(&input, &unpack(end)).unwrap().moves_count(), trace.moves_count());
+    }
+
+    assert_eq!(
+        solve_to_any(&[8, 0, 7, 6, 5, 4, 3, 2, 1], &goals).unwrap().states().last(),
+        Some(pack(&rotated))
+    );
+    assert_eq!(solve_to_any(&rotated, &goals).unwrap().len(), 1);
+    assert!(matches!(
+        solve_to_any(&[1, 2, 3, 4, 5, 6, 8, 7, 0], &goals),
+        Err(SolveError::Unsolvable { .. })
+    ));
+}
+
+#[test]
+fn solving_to_no_goals_fails() {
+    let err = solve_to_any(&[1, 2, 3, 4, 5, 0, 7, 8, 6], &[]).unwrap_err();
+
+    assert!(matches!(err, SolveError::NoGoals));
+    assert_eq!(err.to_string(), "there are no goals to solve towards");
+}
+
+#[test]
+fn exposes_the_goal() {
+    assert_eq!(pack(&goal_array()), GOAL);
+    assert_eq!(unpack(GOAL), goal_array());
+    assert_eq!(goal_with_blank(8), goal_array());
+    assert_eq!(solve(&[1, 2, 3, 4, 5, 6, 7, 0, 8]).unwrap().states().last(), Some(GOAL));
+}
+
+#[test]
+fn compresses_redundant_steps() {
+    let start = pack(&[1, 2, 3, 4, 0, 5, 7, 8, 6]);
+    let moved = right(start);
+    let goal = down(moved);
+    let above = up(start);
+
+    // the blank bumps into the right wall, then goes up and back down, then right, left and right again
+    let steps = vec![start, above, start, moved, right(moved), moved, start, moved, goal, goal];
+    let trace = Trace::from_states(steps).compress();
+
+    assert_eq!(trace.states().collect::<Vec<_>>(), [start, moved, goal]);
+    assert!(verify(&trace).is_ok());
+
+    // cancelled pairs can uncover more pairs to cancel
+    let there_and_back = Trace::from_states(vec![start, above, left(above), above, start]);
+    assert_eq!(there_and_back.compress().states().collect::<Vec<_>>(), [start]);
+
+    let solution = solve(&[8, 6, 7, 2, 5, 4, 3, 0, 1]).unwrap();
+    assert_eq!(
+        solution.compress().states().collect::<Vec<_>>(),
+        solution.states().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn solves_towards_blank_first_goal() {
+    let goal = unpack(goal_blank_first());
+    assert_eq!(goal, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    for seed in 0..10 {
+        let input = random_solvable(seed);
+        let trace = solve_to(&input, &goal).unwrap();
+        assert_eq!(trace.states().last(), Some(goal_blank_first()));
+    }
+
+    let input = [1, 2, 3, 4, 5, 6, 8, 7, 0];
+    assert!(matches!(solve_to(&input, &goal), Err(SolveError::Unsolvable { .. })));
+}
+
+#[test]
+fn compares_traces() {
+    let input = [1, 2, 3, 4, 5, 0, 7, 8, 6];
+    assert_eq!(solve(&input).unwrap(), solve(&input).unwrap());
+    assert_ne!(solve(&input).unwrap(), solve(&goal_array()).unwrap());
+
+    let traces = (0..3).map(|_| solve(&input).unwrap()).collect::<HashSet<_>>();
+    assert_eq!(traces.len(), 1);
+
+    // drawn differently, so not the same trace
+    let alphabet = [' ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+    assert_ne!(solve(&input).unwrap().with_alphabet(&alphabet), solve(&input).unwrap());
+}
+
+#[test]
+fn repairs_unsolvable_input() {
+    let mut input = [1, 2, 3, 4, 5, 6, 8, 7, 0];
+    assert_eq!(make_solvable(&mut input), Some((0, 1)));
+    assert_eq!(input, [2, 1, 3, 4, 5, 6, 8, 7, 0]);
+    assert!(solve(&input).is_ok());
+
+    // the blank tile stays in place
+    let mut input = [0, 2, 1, 3, 4, 5, 6, 7, 8];
+    assert_eq!(make_solvable(&mut input), Some((1, 2)));
+    assert_eq!(input, goal_with_blank(0));
+
+    for seed in 0..10 {
+        let mut input = random_solvable(seed);
+        assert_eq!(make_solvable(&mut input), None);
+        assert_eq!(input, random_solvable(seed));
+    }
+}
+
+#[test]
+fn moves_states_only_within_the_board() {
+    let corner = State(GOAL);
+    assert_eq!(corner.down(), None);
+    assert_eq!(corner.right(), None);
+    assert_eq!(corner.up(), Some(State(up(GOAL))));
+    assert_eq!(corner.left(), Some(State(left(GOAL))));
+
+    let center = State(pack(&goal_with_blank(4)));
+    for next in [center.up(), center.down(), center.left(), center.right()] {
+        assert_ne!(next.unwrap(), center);
+    }
+    assert_eq!(center.up().and_then(State::down), Some(center));
+}
+
+#[test]
+fn tells_solved_states() {
+    assert!(is_solved(GOAL));
+    assert!(is_solved(pack(&goal_array())));
+    assert!(!is_solved(scramble(10, 1)));
+    assert!(!is_solved(goal_blank_first()));
+
+    assert!(is_solved_to(goal_blank_first(), goal_blank_first()));
+    assert!(!is_solved_to(GOAL, goal_blank_first()));
+}
+
+#[test]
+fn reverses_traces() {
+    let input = [1, 2, 3, 4, 0, 5, 7, 8, 6];
+    let trace = solve(&input).unwrap();
+    assert_eq!(trace.states().next(), Some(pack(&input)));
+
+    let reversed = trace.reversed();
+    assert_eq!(reversed.states().collect::<Vec<_>>(), [GOAL, up(GOAL), left(up(GOAL))]);
+    assert_eq!(reversed.states().last(), Some(pack(&input)));
+    assert_eq!(reversed.reversed(), trace);
+}
+
+#[test]
+fn replaying_directions_solves_the_input() {
+    let input = [8, 6, 7, 2, 5, 4, 3, 0, 1];
+    let field = pack(&input);
+
+    let traces = vec![
+        solve(&input).unwrap(),
+        solve_with(&input, &SolveConfig { algorithm: Algorithm::AStar, ..SolveConfig::default() })
+            .unwrap(),
+        solve_with(
+            &input,
+            &SolveConfig { algorithm: Algorithm::IdaStar, ..SolveConfig::default() },
+        )
+        .unwrap(),
+        bidirectional_bfs(field, GOAL).unwrap(),
+    ];
+    for trace in &traces {
+        assert_eq!(replay(field, &trace.directions()), GOAL);
+    }
+
+    let goal = goal_with_blank(0);
+    let trace = solve_to(&input, &goal).unwrap();
+    assert_eq!(replay(field, &trace.directions()), pack(&goal));
+}
+
+#[test]
+fn tiles_slide_against_the_blank() {
+    // the blank tile goes down, so 6 slides up
+    let trace = solve(&[1, 2, 3, 4, 5, 0, 7, 8, 6]).unwrap();
+    assert_eq!(trace.directions(), [Direction::Down]);
+    assert_eq!(trace.tile_moves(), [Direction::Up]);
+
+    // the blank tile goes right and down, so 5 slides left and 6 up
+    let trace = solve(&[1, 2, 3, 4, 0, 5, 7, 8, 6]).unwrap();
+    assert_eq!(trace.tile_moves(), [Direction::Left, Direction::Up]);
+}
+
+#[test]
+fn applies_directions() {
+    let field = pack(&goal_with_blank(4));
+    let directions = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+    let moved = [up(field), down(field), left(field), right(field)];
+
+    for (&direction, &expected) in directions.iter().zip(&moved) {
+        assert_eq!(apply(field, direction), expected);
+        assert_eq!(apply(apply(field, direction), direction.opposite()), field);
+        assert_eq!(direction.opposite().opposite(), direction);
+    }
+
+    // into a wall
+    assert_eq!(apply(GOAL, Direction::Right), GOAL);
+}
+
+#[test]
+fn hints_lead_to_the_goal() {
+    assert_eq!(hint(GOAL), None);
+    assert_eq!(hint(pack(&[1, 2, 3, 4, 5, 0, 7, 8, 6])), Some(Direction::Down));
+    assert_eq!(hint(pack(&[1, 2, 3, 4, 5, 6, 8, 7, 0])), None);
+
+    // following the hints takes exactly as many moves as the optimal solution
+    let input = [8, 6, 7, 2, 5, 4, 3, 0, 1];
+    let mut field = pack(&input);
+    let mut moves = 0;
+    while let Some(direction) = hint(field) {
+        field = apply(field, direction);
+        moves += 1;
+    }
+    assert_eq!(field, GOAL);
+    assert_eq!(moves, solve(&input).unwrap().moves_count());
+}
+
+#[test]
+fn bfs_grows_containers_beyond_its_capacity() {
+    let easy = pack(&[1, 2, 3, 4, 5, 0, 7, 8, 6]);
+    let (trace, stats) = bfs_stats_with_capacity(easy, GOAL, 16).unwrap();
+    assert_eq!(trace.moves_count(), 1);
+    assert_eq!(stats.reallocations, 0);
+
+    let hard = pack(&[8, 6, 7, 2, 5, 4, 3, 0, 1]);
+    let (trace, stats) = bfs_stats_with_capacity(hard, GOAL, 16).unwrap();
+    assert_eq!(trace.moves_count(), 31);
+    assert!(stats.reallocations > 0);
+    assert_eq!(bfs_stats_with_capacity(hard, GOAL, BFS_CAPACITY).unwrap().1.reallocations, 0);
+
+    let cfg = SolveConfig { capacity: Some(16), ..SolveConfig::default() };
+    assert_eq!(solve_with(&unpack(hard), &cfg).unwrap(), trace);
+}
+
+#[test]
+fn directions_of_broken_traces() {
+    let start = pack(&[1, 2, 3, 4, 0, 5, 7, 8, 6]);
+
+    let repeated = Trace::from_states(vec![start, right(start), right(start)]);
+    assert!(matches!(repeated.try_directions(), Err(SolveError::IllegalMove(2))));
+    assert_eq!(repeated.compress().try_directions().unwrap(), [Direction::Right]);
+
+    // the blank tile can't wrap around from the end of a row to the start of the next one
+    let end_of_row = pack(&[1, 2, 0, 3, 4, 5, 6, 7, 8]);
+    let wrapped = pack(&[1, 2, 3, 0, 4, 5, 6, 7, 8]);
+    let trace = Trace::from_states(vec![end_of_row, wrapped]);
+    assert!(matches!(trace.try_directions(), Err(SolveError::IllegalMove(1))));
+
+    let jumped = Trace::from_states(vec![start, GOAL]);
+    assert!(matches!(jumped.try_directions(), Err(SolveError::IllegalMove(1))));
+}
+
+#[test]
+#[should_panic(expected = "not a single move away")]
+fn directions_panic_on_broken_traces() {
+    Trace::from_states(vec![pack(&[1, 2, 3, 4, 0, 5, 7, 8, 6]), GOAL]).directions();
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "std")]
+
+use superzub::{
+    anti_transpose, bfs, canonical, count_inversions, distance_table, distance_table_canonical,
+    goal_with_blank, pack, random_solvable, reflect_horizontal, reflect_vertical, rotate180,
+    rotate270, rotate90, scramble, solve_with, solve_with_progress, symmetries, transpose,
+    transpose_relabeled, unpack, verify, SolveConfig, GOAL,
+};
+
+#[test]
+fn four_quarter_turns_are_identity() {
+    for seed in 0..50 {
+        let field = scramble(30, seed);
+        assert_eq!(rotate90(rotate90(rotate90(rotate90(field)))), field);
+        assert_eq!(rotate90(rotate90(field)), rotate180(field));
+        assert_eq!(rotate90(rotate270(field)), field);
+    }
+}
+
+#[test]
+fn reflections_undo_themselves() {
+    for seed in 0..50 {
+        let field = scramble(30, seed);
+        for reflect in [reflect_horizontal, reflect_vertical, transpose, anti_transpose] {
+            assert_eq!(reflect(reflect(field)), field);
+        }
+        assert_eq!(reflect_vertical(reflect_horizontal(field)), rotate180(field));
+    }
+}
+
+#[test]
+fn remaps_tile_positions() {
+    assert_eq!(unpack(rotate90(GOAL)), [7, 4, 1, 8, 5, 2, 0, 6, 3]);
+    assert_eq!(unpack(reflect_horizontal(GOAL)), [3, 2, 1, 6, 5, 4, 0, 8, 7]);
+    assert_eq!(unpack(transpose(GOAL)), [1, 4, 7, 2, 5, 8, 3, 6, 0]);
+    assert_eq!(
+        unpack(anti_transpose(pack(&[0, 1, 2, 3, 4, 5, 6, 7, 8]))),
+        [8, 5, 2, 7, 4, 1, 6, 3, 0]
+    );
+
+    // tiles keep their values, so a mirrored goal can be in the other half of the state space
+    assert_eq!(count_inversions(&unpack(reflect_horizontal(GOAL))) % 2, 1);
+
+    let mut all = symmetries(GOAL).to_vec();
+    all.sort_unstable();
+    all.dedup();
+    assert_eq!(all.len(), 8);
+}
+
+#[test]
+fn relabeled_transposition_preserves_the_goal() {
+    assert_eq!(transpose_relabeled(GOAL), GOAL);
+    assert_eq!(canonical(GOAL), GOAL);
+    assert!(symmetries(GOAL)[1..].iter().all(|&field| field != GOAL));
+
+    // a move of the blank to the left from the goal turns into a move up
+    let field = pack(&[1, 2, 3, 4, 5, 6, 7, 0, 8]);
+    assert_eq!(unpack(transpose_relabeled(field)), [1, 2, 3, 4, 5, 0, 7, 8, 6]);
+    assert_eq!(canonical(field), canonical(transpose_relabeled(field)));
+}
+
+#[test]
+fn canonical_table_keeps_distances() {
+    let table = distance_table();
+    let canonical_table = distance_table_canonical();
+
+    assert!(canonical_table.len() > table.len() / 2 && canonical_table.len() < table.len() * 3 / 5);
+    for (&field, &distance) in &table {
+        assert_eq!(canonical_table[&canonical(field)], distance);
+    }
+}
+
+#[test]
+fn bfs_over_canonical_states_finds_optimal_traces() {
+    let cfg = SolveConfig { canonical: true, ..SolveConfig::default() };
+
+    for seed in 0..20 {
+        let input = random_solvable(seed);
+        let trace = solve_with(&input, &cfg).unwrap();
+
+        assert_eq!(trace.states().next(), Some(pack(&input)));
+        assert_eq!(trace.states().last(), Some(GOAL));
+        assert!(verify(&trace).is_ok());
+        assert_eq!(trace.moves_count(), bfs(pack(&input), GOAL).unwrap().moves_count());
+
+        // one of the two is only ever reached through the other, whose path has to be mirrored back
+        let mirrored = unpack(transpose_relabeled(pack(&input)));
+        let trace_of_mirrored = solve_with(&mirrored, &cfg).unwrap();
+        assert_eq!(trace_of_mirrored.states().next(), Some(pack(&mirrored)));
+        assert!(verify(&trace_of_mirrored).is_ok());
+        assert_eq!(trace_of_mirrored.moves_count(), trace.moves_count());
+    }
+
+    // a goal the symmetry moves can't use it
+    let goal = goal_with_blank(0);
+    let input = random_solvable(1);
+    let towards = SolveConfig { goal: Some(goal), ..cfg };
+    let trace = solve_with(&input, &towards).unwrap();
+    assert_eq!(trace.states().last(), Some(pack(&goal)));
+    assert_eq!(trace.moves_count(), bfs(pack(&input), pack(&goal)).unwrap().moves_count());
+}
+
+#[test]
+fn bfs_over_canonical_states_expands_fewer() {
+    let input = [8, 6, 7, 2, 5, 4, 3, 0, 1];
+    let expanded = |canonical| {
+        let cfg = SolveConfig { canonical, ..SolveConfig::default() };
+        let mut last = 0;
+        solve_with_progress(&input, &cfg, Some(&mut |p| last = p.expanded)).unwrap();
+        last
+    };
+
+    let (full, reduced) = (expanded(false), expanded(true));
+    assert!(reduced < full * 2 / 3, "{} {}", reduced, full);
+}
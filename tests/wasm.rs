@@ -0,0 +1,14 @@
+#![cfg(feature = "wasm")]
+
+use superzub::wasm::solve_json;
+
+#[test]
+fn solves_to_json() {
+    assert_eq!(
+        solve_json("1 2 3 4 5 6 7 0 8"),
+        r#"{"moves":["R"],"states":[[1,2,3,4,5,6,7,0,8],[1,2,3,4,5,6,7,8,0]]}"#
+    );
+    assert!(
+        solve_json("1 2 3").starts_with(r#"{"error":"found 3 tiles, which don't fill the board"#)
+    );
+}